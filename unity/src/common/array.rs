@@ -0,0 +1,230 @@
+//! TODO
+
+use std::{ffi::c_void, fmt, marker::PhantomData, mem, ptr, slice};
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::{
+    class::UnityClass,
+    object::{ManagedObject, UnityObject},
+    value::ManagedValue,
+};
+
+/// where the elements of an array start, after the object header, the bounds and the length
+///
+/// the same on mono and il2cpp
+const DATA_OFFSET: usize = 4 * mem::size_of::<usize>();
+
+/// Represents a C# Array
+///
+/// `T` is the element as it's stored in the array, a primitive or `#[repr(C)]` struct for value types,
+/// [`UnityObject`] or another wrapper for reference types.
+///
+/// # Examples
+///
+/// ```no_run
+/// use unity_rs::common::{array::UnityArray, class::UnityClass};
+///
+/// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+/// let byte = UnityClass::get("mscorlib", "System", "Byte")?;
+/// let buffer = UnityArray::from_slice(&byte, b"hello")?;
+///
+/// assert_eq!(buffer.len()?, 5);
+/// assert_eq!(buffer.iter()?.collect::<Vec<u8>>(), b"hello");
+/// # Ok(())
+/// # }
+/// ```
+#[repr(transparent)]
+pub struct UnityArray<T> {
+    /// The inner pointer to the Array
+    pub inner: *mut c_void,
+    pd: PhantomData<T>,
+}
+
+unsafe impl<T> Send for UnityArray<T> {}
+unsafe impl<T> Sync for UnityArray<T> {}
+
+impl<T> Clone for UnityArray<T> {
+    fn clone(&self) -> UnityArray<T> {
+        UnityArray { ..*self }
+    }
+}
+
+impl<T> fmt::Debug for UnityArray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnityArray").field("inner", &self.inner).finish()
+    }
+}
+
+impl<T: ManagedValue> UnityArray<T> {
+    /// creates an array of `len` default elements of `element_class`
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::SizeMismatch` - if `T` doesn't have the size of an element
+    pub fn new(element_class: &UnityClass, len: usize) -> Result<UnityArray<T>, RuntimeError> {
+        UnityArray::from_object(get_runtime()?.new_array(element_class, len)?)
+    }
+
+    /// creates an array of `element_class` holding `values`
+    pub fn from_slice(element_class: &UnityClass, values: &[T]) -> Result<UnityArray<T>, RuntimeError>
+    where
+        T: Clone,
+    {
+        let array = UnityArray::new(element_class, values.len())?;
+
+        for (index, value) in values.iter().enumerate() {
+            array.set(index, value.clone())?;
+        }
+
+        Ok(array)
+    }
+
+    /// treats `object` as an array of `T`, like one returned by [`UnityMethod::invoke`](super::method::UnityMethod::invoke)
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::NullPointer` - if `object` is null
+    /// * `RuntimeError::SizeMismatch` - if `T` doesn't have the size of an element
+    pub fn from_object(object: UnityObject) -> Result<UnityArray<T>, RuntimeError> {
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        let element_size = get_runtime()?.get_array_element_size(&object)?;
+
+        if element_size != mem::size_of::<T>() {
+            return Err(RuntimeError::SizeMismatch {
+                expected: element_size,
+                got: mem::size_of::<T>(),
+            });
+        }
+
+        Ok(UnityArray {
+            inner: object.inner,
+            pd: PhantomData,
+        })
+    }
+
+    /// returns the number of elements
+    pub fn len(&self) -> Result<usize, RuntimeError> {
+        get_runtime()?.get_array_length(&self.as_object())
+    }
+
+    /// whether the array has no elements
+    pub fn is_empty(&self) -> Result<bool, RuntimeError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// returns a copy of the element at `index`, `None` if it's out of range
+    pub fn get(&self, index: usize) -> Result<Option<T>, RuntimeError>
+    where
+        T: Clone,
+    {
+        if index >= self.len()? {
+            return Ok(None);
+        }
+
+        Ok(Some(unsafe { ptr::read(self.data().add(index)) }))
+    }
+
+    /// overwrites the element at `index`
+    ///
+    /// object references are stored through the gc write barrier.
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::IndexOutOfRange` - if `index` is out of range
+    pub fn set(&self, index: usize, mut value: T) -> Result<(), RuntimeError> {
+        let len = self.len()?;
+
+        if index >= len {
+            return Err(RuntimeError::IndexOutOfRange { index, len });
+        }
+
+        let slot = unsafe { self.data().add(index) };
+
+        if !T::is_reference() {
+            unsafe { ptr::write(slot, value) };
+            return Ok(());
+        }
+
+        let value = UnityObject {
+            inner: value.as_arg(),
+        };
+
+        unsafe { get_runtime()?.set_array_reference(&self.as_object(), slot.cast(), &value) }
+    }
+
+    /// returns copies of all elements, each read when the iterator reaches it
+    pub fn iter(&self) -> Result<impl Iterator<Item = T> + '_, RuntimeError>
+    where
+        T: Clone,
+    {
+        let len = self.len()?;
+
+        // managed arrays can't be resized, so the length stays valid
+        Ok((0..len).map(move |index| unsafe { ptr::read(self.data().add(index)) }))
+    }
+
+    /// returns the elements in place
+    ///
+    /// # Safety
+    ///
+    /// the slice points into managed memory, so the array has to stay alive and in place while it's used,
+    /// for example by pinning it with [`GcHandle::pinned`](super::gc_handle::GcHandle::pinned).
+    /// no managed code may write to the array while the slice is alive.
+    pub unsafe fn as_slice(&self) -> Result<&[T], RuntimeError> {
+        let len = self.len()?;
+
+        Ok(slice::from_raw_parts(self.data(), len))
+    }
+
+    /// returns the elements in place, for writing
+    ///
+    /// # Safety
+    ///
+    /// object references written through the slice skip the gc write barrier, use [`UnityArray::set`] for those.
+    /// no managed code may access the array while the slice is alive.
+    pub unsafe fn as_mut_slice(&mut self) -> Result<&mut [T], RuntimeError> {
+        let len = self.len()?;
+
+        Ok(slice::from_raw_parts_mut(self.data(), len))
+    }
+
+    /// the array as a plain object
+    pub fn as_object(&self) -> UnityObject {
+        UnityObject {
+            inner: self.inner,
+        }
+    }
+
+    fn data(&self) -> *mut T {
+        unsafe { self.inner.cast::<u8>().add(DATA_OFFSET).cast() }
+    }
+}
+
+unsafe impl<T> ManagedValue for UnityArray<T> {
+    fn is_reference() -> bool {
+        true
+    }
+
+    fn as_arg(&mut self) -> *mut c_void {
+        self.inner
+    }
+}
+
+unsafe impl<T> ManagedObject for UnityArray<T> {
+    fn from_object(object: UnityObject) -> UnityArray<T> {
+        UnityArray {
+            inner: object.inner,
+            pd: PhantomData,
+        }
+    }
+
+    fn as_object(&self) -> UnityObject {
+        UnityObject {
+            inner: self.inner,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! TODO
+
+use std::ffi::c_void;
+
+use crate::{
+    mono::types::AssemblyName,
+    runtime::{get_runtime, RuntimeError},
+};
+
+use super::image::UnityImage;
+
+/// Represents a C# Assembly
+#[derive(Debug)]
+pub struct UnityAssembly {
+    /// The inner pointer to the Assembly
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityAssembly {}
+unsafe impl Sync for UnityAssembly {}
+
+impl Clone for UnityAssembly {
+    fn clone(&self) -> UnityAssembly {
+        UnityAssembly { ..*self }
+    }
+}
+
+impl UnityAssembly {
+    /// returns the image of the assembly, which holds its classes
+    pub fn image(&self) -> Result<UnityImage, RuntimeError> {
+        get_runtime()?.get_assembly_image(self)
+    }
+
+    /// returns the name of the assembly
+    ///
+    /// il2cpp doesn't expose assembly names, so there only `name` is filled in,
+    /// taken from the image name without its extension.
+    pub fn name(&self) -> Result<AssemblyName, RuntimeError> {
+        get_runtime()?.get_assembly_name(self)
+    }
+}
@@ -0,0 +1,98 @@
+//! TODO
+
+use std::{
+    any::Any,
+    ffi::CString,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::runtime::get_runtime;
+
+/// runs `f`, catching a panic instead of letting it unwind into the runtime
+///
+/// every callback the runtime calls into has to be wrapped in this, or [`catch_managed`],
+/// since unwinding into native frames is undefined behavior.
+/// a panic is logged along with `name`, and `None` returned, so the caller can return a safe default.
+///
+/// # Arguments
+///
+/// * `name` - what the callback is, for the log
+/// * `f` - the body of the callback
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::c_void;
+///
+/// use unity_rs::common::callback;
+///
+/// extern "C" fn on_event(data: *mut c_void) -> i32 {
+///     callback::catch("on_event", || {
+///         if data.is_null() {
+///             panic!("no data");
+///         }
+///
+///         1
+///     })
+///     .unwrap_or(0)
+/// }
+///
+/// assert_eq!(on_event(std::ptr::null_mut()), 0);
+/// ```
+pub fn catch<R>(name: &str, f: impl FnOnce() -> R) -> Option<R> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Some(value),
+        Err(payload) => {
+            log::error!("{} panicked: {}", name, panic_message(&*payload));
+            None
+        }
+    }
+}
+
+/// runs `f` like [`catch`], but raises a panic as a `System.Exception` in the managed caller
+///
+/// only returns `None` if the exception couldn't be raised, in which case the caller should return a safe default.
+///
+/// # Safety
+///
+/// the callback has to be called from managed code, such as an internal call,
+/// and has to be `extern "C-unwind"`, since raising unwinds through it.
+/// nothing in the frames between the callback and this may need dropping, as it may not be.
+pub unsafe fn catch_managed<R>(name: &str, f: impl FnOnce() -> R) -> Option<R> {
+    // the payload is dropped at the end of the arm
+    let text = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => return Some(value),
+        Err(payload) => format!("{} panicked: {}", name, panic_message(&*payload)),
+    };
+
+    log::error!("{}", text);
+
+    // a nul byte would cut the message short
+    let message = CString::new(text.replace('\0', "\\0")).unwrap_or_default();
+    drop(text);
+
+    let error = match get_runtime() {
+        Ok(runtime) => {
+            let exception = runtime.new_exception("System", "Exception", &message);
+
+            // raising jumps past this frame on mono, so everything rust owns has to be dropped first
+            drop(message);
+
+            match exception.and_then(|exception| runtime.raise_exception(exception)) {
+                Err(error) => error,
+            }
+        }
+        Err(error) => error,
+    };
+
+    log::error!("failed to raise the panic of {}: {}", name, error);
+    None
+}
+
+/// the message a panic was started with
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload.downcast_ref::<String>().map_or("Box<dyn Any>", String::as_str),
+    }
+}
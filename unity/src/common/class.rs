@@ -0,0 +1,222 @@
+//! TODO
+
+use std::{ffi::c_void, fmt, ptr};
+
+use crate::runtime::{get_runtime, Runtime, RuntimeError};
+
+use super::{field::UnityField, method::UnityMethod, property::UnityProperty};
+
+/// `TYPE_ATTRIBUTE_INTERFACE` from ECMA-335
+const TYPE_ATTRIBUTE_INTERFACE: i32 = 0x0020;
+/// `TYPE_ATTRIBUTE_ABSTRACT` from ECMA-335
+const TYPE_ATTRIBUTE_ABSTRACT: i32 = 0x0080;
+/// `TYPE_ATTRIBUTE_SEALED` from ECMA-335
+const TYPE_ATTRIBUTE_SEALED: i32 = 0x0100;
+
+/// Represents a C# Class
+#[derive(Debug)]
+pub struct UnityClass {
+    /// The inner pointer to the Class
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityClass {}
+unsafe impl Sync for UnityClass {}
+
+impl Clone for UnityClass {
+    fn clone(&self) -> UnityClass {
+        UnityClass { ..*self }
+    }
+}
+
+impl UnityClass {
+    /// looks up a class by assembly, namespace and name
+    ///
+    /// # Arguments
+    ///
+    /// * `assembly` - the name of the assembly, e.g. `Assembly-CSharp`
+    /// * `namespace` - the namespace of the class, empty for the global namespace
+    /// * `name` - the name of the class
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::class::UnityClass;
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("UnityEngine.CoreModule", "UnityEngine", "GameObject")?;
+    /// assert_eq!(class.get_name()?, "GameObject");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(assembly: &str, namespace: &str, name: &str) -> Result<UnityClass, RuntimeError> {
+        get_runtime()?.get_class(assembly, namespace, name)
+    }
+
+    /// returns the name of the class
+    pub fn get_name(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_class_name(self)
+    }
+
+    /// returns the namespace of the class
+    pub fn get_namespace(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_class_namespace(self)
+    }
+
+    /// looks up a method declared on this class, see [`UnityMethod::get`]
+    pub fn get_method(&self, name: &str, param_count: i32) -> Result<UnityMethod, RuntimeError> {
+        UnityMethod::get(self, name, param_count)
+    }
+
+    /// looks up the overload of a method with exactly the given parameter types, see [`UnityMethod::find`]
+    pub fn find_method(&self, name: &str, param_types: &[&str]) -> Result<UnityMethod, RuntimeError> {
+        UnityMethod::find(self, name, param_types)
+    }
+
+    /// looks up a field declared on this class, see [`UnityField::get`]
+    pub fn get_field(&self, name: &str) -> Result<UnityField, RuntimeError> {
+        UnityField::get(self, name)
+    }
+
+    /// looks up a property declared on this class, see [`UnityProperty::find`]
+    pub fn get_property(&self, name: &str) -> Result<UnityProperty, RuntimeError> {
+        UnityProperty::find(self, name)
+    }
+
+    /// returns the class this class derives from, `None` for `System.Object` and interfaces
+    pub fn get_parent(&self) -> Result<Option<UnityClass>, RuntimeError> {
+        get_runtime()?.get_class_parent(self)
+    }
+
+    /// returns the attribute flags of the class
+    pub fn get_flags(&self) -> Result<i32, RuntimeError> {
+        get_runtime()?.get_class_flags(self)
+    }
+
+    /// whether the class is an interface
+    pub fn is_interface(&self) -> Result<bool, RuntimeError> {
+        Ok(self.get_flags()? & TYPE_ATTRIBUTE_INTERFACE != 0)
+    }
+
+    /// whether the class is abstract, which includes interfaces and static classes
+    pub fn is_abstract(&self) -> Result<bool, RuntimeError> {
+        Ok(self.get_flags()? & TYPE_ATTRIBUTE_ABSTRACT != 0)
+    }
+
+    /// whether the class is static, which C# compiles to abstract and sealed
+    pub fn is_static(&self) -> Result<bool, RuntimeError> {
+        const STATIC: i32 = TYPE_ATTRIBUTE_ABSTRACT | TYPE_ATTRIBUTE_SEALED;
+        let flags = self.get_flags()?;
+
+        Ok(flags & TYPE_ATTRIBUTE_INTERFACE == 0 && flags & STATIC == STATIC)
+    }
+
+    /// whether the class is generic, either a definition like `List<T>` or an instance like `List<int>`
+    ///
+    /// on mono only instances are recognized, see [`Runtime::is_class_generic`].
+    pub fn is_generic(&self) -> Result<bool, RuntimeError> {
+        get_runtime()?.is_class_generic(self)
+    }
+
+    /// whether the class is a value type
+    pub fn is_value_type(&self) -> Result<bool, RuntimeError> {
+        get_runtime()?.is_value_type(self)
+    }
+
+    /// iterates over the methods declared on this class, not including inherited ones
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::class::UnityClass;
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("UnityEngine.CoreModule", "UnityEngine", "Transform")?;
+    ///
+    /// for method in class.methods()? {
+    ///     let method = method?;
+    ///     println!("{}({} params)", method.get_name()?, method.get_param_count()?);
+    /// }
+    ///
+    /// let mut parent = class.get_parent()?;
+    /// while let Some(class) = parent {
+    ///     println!("derives from {}", class.get_name()?);
+    ///     parent = class.get_parent()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn methods(&self) -> Result<ClassMembers<UnityMethod>, RuntimeError> {
+        ClassMembers::new(self, |runtime, class, iter| runtime.next_class_method(class, iter))
+    }
+
+    /// iterates over the fields declared on this class, not including inherited ones
+    pub fn fields(&self) -> Result<ClassMembers<UnityField>, RuntimeError> {
+        ClassMembers::new(self, |runtime, class, iter| runtime.next_class_field(class, iter))
+    }
+
+    /// iterates over the properties declared on this class, not including inherited ones
+    pub fn properties(&self) -> Result<ClassMembers<UnityProperty>, RuntimeError> {
+        ClassMembers::new(self, |runtime, class, iter| runtime.next_class_property(class, iter))
+    }
+
+    /// iterates over the classes nested in this class
+    pub fn nested_types(&self) -> Result<ClassMembers<UnityClass>, RuntimeError> {
+        ClassMembers::new(self, |runtime, class, iter| runtime.next_nested_type(class, iter))
+    }
+
+    /// iterates over the interfaces this class implements itself, not including ones of its parents
+    pub fn interfaces(&self) -> Result<ClassMembers<UnityClass>, RuntimeError> {
+        ClassMembers::new(self, |runtime, class, iter| runtime.next_class_interface(class, iter))
+    }
+}
+
+/// advances the runtime's cursor over one kind of member
+type NextMember<T> = fn(&dyn Runtime, &UnityClass, &mut *mut c_void) -> Result<Option<T>, RuntimeError>;
+
+/// An iterator over the members of a class, such as [`UnityClass::methods`]
+///
+/// walks the `void** iter` cursor of the runtime, stopping after the first error.
+pub struct ClassMembers<T> {
+    runtime: &'static dyn Runtime,
+    class: UnityClass,
+    iter: *mut c_void,
+    next: NextMember<T>,
+    done: bool,
+}
+
+impl<T> ClassMembers<T> {
+    fn new(class: &UnityClass, next: NextMember<T>) -> Result<ClassMembers<T>, RuntimeError> {
+        Ok(ClassMembers {
+            runtime: get_runtime()?,
+            class: class.clone(),
+            iter: ptr::null_mut(),
+            next,
+            done: false,
+        })
+    }
+}
+
+impl<T> Iterator for ClassMembers<T> {
+    type Item = Result<T, RuntimeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let member = (self.next)(self.runtime, &self.class, &mut self.iter).transpose();
+        self.done = !matches!(member, Some(Ok(_)));
+
+        member
+    }
+}
+
+impl<T> fmt::Debug for ClassMembers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClassMembers")
+            .field("class", &self.class)
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
@@ -2,6 +2,10 @@
 
 use std::ffi::c_void;
 
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::assembly::UnityAssembly;
+
 /// Represents a C# Appdomain
 #[derive(Debug)]
 pub struct UnityDomain {
@@ -17,3 +21,34 @@ impl Clone for UnityDomain {
         UnityDomain { ..*self }
     }
 }
+
+impl UnityDomain {
+    /// returns all loaded assemblies
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::runtime::get_runtime;
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let domain = get_runtime()?.get_domain()?;
+    ///
+    /// for assembly in domain.assemblies()? {
+    ///     println!("{}", assembly.name()?.get_name()?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assemblies(&self) -> Result<Vec<UnityAssembly>, RuntimeError> {
+        get_runtime()?.get_assemblies(self)
+    }
+
+    /// opens an assembly, loading it if it isn't loaded yet
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path to the assembly, or the name of an already loaded one
+    pub fn open_assembly(&self, path: &str) -> Result<UnityAssembly, RuntimeError> {
+        get_runtime()?.open_assembly(self, path)
+    }
+}
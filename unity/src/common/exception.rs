@@ -0,0 +1,148 @@
+//! TODO
+
+use std::{
+    error,
+    fmt::{self, Display},
+};
+
+use crate::runtime::Runtime;
+
+use super::class::UnityClass;
+
+/// A C# exception thrown out of managed code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedException {
+    /// The full name of the exception class, e.g. `System.NullReferenceException`
+    pub class: String,
+    /// The `Message` of the exception
+    pub message: String,
+    /// The `StackTrace` of the exception, empty if it couldn't be read
+    pub stack_trace: String,
+}
+
+impl ManagedException {
+    /// the full name of an exception class, falling back to `System.Exception`
+    pub(crate) fn class_name(runtime: &dyn Runtime, class: Option<UnityClass>) -> String {
+        let Some(class) = class else {
+            return "System.Exception".to_string();
+        };
+
+        match (runtime.get_class_namespace(&class), runtime.get_class_name(&class)) {
+            (Ok(namespace), Ok(name)) if !namespace.is_empty() => format!("{}.{}", namespace, name),
+            (_, Ok(name)) => name,
+            _ => "System.Exception".to_string(),
+        }
+    }
+
+    /// splits the output of `Exception.ToString` into message and stack trace
+    ///
+    /// `ToString` prints `Class: Message`, followed by one `  at ...` line per frame. windows line endings are
+    /// normalized to `\n`.
+    pub(crate) fn from_to_string(class: String, text: &str) -> ManagedException {
+        let text = text.replace("\r\n", "\n");
+
+        let text = match text.strip_prefix(class.as_str()) {
+            Some(rest) => rest.strip_prefix(": ").unwrap_or(rest),
+            None => text.as_str(),
+        };
+
+        let (message, stack_trace) = match text.find("\n  at ") {
+            Some(index) => (&text[..index], &text[index + 1..]),
+            None => (text, ""),
+        };
+
+        ManagedException {
+            class,
+            message: message.trim_end().to_string(),
+            stack_trace: stack_trace.trim_end().to_string(),
+        }
+    }
+}
+
+impl Display for ManagedException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.class, self.message)?;
+
+        if !self.stack_trace.is_empty() {
+            write!(f, "\n{}", self.stack_trace)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for ManagedException {}
+
+#[cfg(test)]
+mod tests {
+    use super::ManagedException;
+
+    fn parse(class: &str, text: &str) -> ManagedException {
+        ManagedException::from_to_string(class.to_string(), text)
+    }
+
+    #[test]
+    fn message_and_stack_trace_are_split() {
+        let exception = parse(
+            "System.NullReferenceException",
+            "System.NullReferenceException: Object reference not set\n  at Player.Update () [0x00000]\n  at Game.Tick () [0x0000c]",
+        );
+
+        assert_eq!(exception.message, "Object reference not set");
+        assert_eq!(exception.stack_trace, "  at Player.Update () [0x00000]\n  at Game.Tick () [0x0000c]");
+    }
+
+    #[test]
+    fn windows_line_endings_are_normalized() {
+        let exception = parse("System.Exception", "System.Exception: boom\r\n  at Player.Update ()\r\n  at Game.Tick ()");
+
+        assert_eq!(exception.message, "boom");
+        assert_eq!(exception.stack_trace, "  at Player.Update ()\n  at Game.Tick ()");
+    }
+
+    #[test]
+    fn windows_line_endings_are_normalized_in_multi_line_messages() {
+        let exception = parse("System.Exception", "System.Exception: first\r\nsecond\r\nthird\r\n  at Game.Tick ()\r\n");
+
+        assert_eq!(exception.message, "first\nsecond\nthird");
+        assert_eq!(exception.stack_trace, "  at Game.Tick ()");
+    }
+
+    #[test]
+    fn missing_stack_trace_leaves_it_empty() {
+        let exception = parse("System.InvalidOperationException", "System.InvalidOperationException: not now\n");
+
+        assert_eq!(exception.message, "not now");
+        assert_eq!(exception.stack_trace, "");
+    }
+
+    #[test]
+    fn classes_without_a_namespace_are_stripped() {
+        let exception = parse("GameException", "GameException: level missing\n  at Loader.Load ()");
+
+        assert_eq!(exception.message, "level missing");
+        assert_eq!(exception.stack_trace, "  at Loader.Load ()");
+    }
+
+    #[test]
+    fn inner_exceptions_stay_in_the_message() {
+        let exception = parse(
+            "System.Exception",
+            "System.Exception: outer ---> System.ArgumentException: inner\n  at Inner.Throw ()\n   --- End of inner exception stack trace ---\n  at Outer.Throw ()",
+        );
+
+        assert_eq!(exception.message, "outer ---> System.ArgumentException: inner");
+        assert_eq!(
+            exception.stack_trace,
+            "  at Inner.Throw ()\n   --- End of inner exception stack trace ---\n  at Outer.Throw ()"
+        );
+    }
+
+    #[test]
+    fn multi_line_messages_are_kept() {
+        let exception = parse("System.Exception", "System.Exception: first\nsecond\n  at Game.Tick ()");
+
+        assert_eq!(exception.message, "first\nsecond");
+        assert_eq!(exception.stack_trace, "  at Game.Tick ()");
+    }
+}
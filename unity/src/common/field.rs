@@ -0,0 +1,138 @@
+//! TODO
+
+use std::{ffi::c_void, mem::{self, MaybeUninit}};
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::{class::UnityClass, object::UnityObject, ty::UnityType, value::ManagedValue};
+
+/// `FIELD_ATTRIBUTE_STATIC` from ECMA-335
+const FIELD_ATTRIBUTE_STATIC: i32 = 0x0010;
+
+/// Represents a C# Field
+#[derive(Debug)]
+pub struct UnityField {
+    /// The inner pointer to the Field
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityField {}
+unsafe impl Sync for UnityField {}
+
+impl Clone for UnityField {
+    fn clone(&self) -> UnityField {
+        UnityField { ..*self }
+    }
+}
+
+impl UnityField {
+    /// looks up a field on a class
+    pub fn get(class: &UnityClass, name: &str) -> Result<UnityField, RuntimeError> {
+        get_runtime()?.get_field(class, name)
+    }
+
+    /// returns the name of the field
+    pub fn get_name(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_field_name(self)
+    }
+
+    /// returns the offset of the field within its object, including the object header
+    pub fn get_offset(&self) -> Result<usize, RuntimeError> {
+        get_runtime()?.get_field_offset(self)
+    }
+
+    /// returns the type of the field
+    pub fn get_type(&self) -> Result<UnityType, RuntimeError> {
+        get_runtime()?.get_field_type(self)
+    }
+
+    /// returns the attribute flags of the field
+    pub fn get_flags(&self) -> Result<i32, RuntimeError> {
+        get_runtime()?.get_field_flags(self)
+    }
+
+    /// whether the field is static
+    pub fn is_static(&self) -> Result<bool, RuntimeError> {
+        Ok(self.get_flags()? & FIELD_ATTRIBUTE_STATIC != 0)
+    }
+
+    /// reads an instance field
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::FieldIsStatic` - if the field is static, use [`UnityField::get_static`]
+    /// * `RuntimeError::SizeMismatch` - if `T` doesn't have the size of the field
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::{class::UnityClass, object::UnityObject};
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("Assembly-CSharp", "", "Player")?;
+    /// let player: UnityObject = class.get_field("instance")?.get_static()?;
+    ///
+    /// let health = class.get_field("health")?;
+    ///
+    /// let value: f32 = health.get_value(&player)?;
+    /// health.set_value(&player, value + 10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_value<T: ManagedValue>(&self, instance: &UnityObject) -> Result<T, RuntimeError> {
+        self.check_instance(false)?;
+        self.check_size::<T>()?;
+
+        let mut value = MaybeUninit::<T>::zeroed();
+        get_runtime()?.get_field_value(self, instance, value.as_mut_ptr().cast())?;
+
+        Ok(unsafe { value.assume_init() })
+    }
+
+    /// writes an instance field
+    pub fn set_value<T: ManagedValue>(&self, instance: &UnityObject, mut value: T) -> Result<(), RuntimeError> {
+        self.check_instance(false)?;
+        self.check_size::<T>()?;
+
+        get_runtime()?.set_field_value(self, instance, value.as_arg())
+    }
+
+    /// reads a static field
+    pub fn get_static<T: ManagedValue>(&self) -> Result<T, RuntimeError> {
+        self.check_instance(true)?;
+        self.check_size::<T>()?;
+
+        let mut value = MaybeUninit::<T>::zeroed();
+        get_runtime()?.get_static_field_value(self, value.as_mut_ptr().cast())?;
+
+        Ok(unsafe { value.assume_init() })
+    }
+
+    /// writes a static field
+    pub fn set_static<T: ManagedValue>(&self, mut value: T) -> Result<(), RuntimeError> {
+        self.check_instance(true)?;
+        self.check_size::<T>()?;
+
+        get_runtime()?.set_static_field_value(self, value.as_arg())
+    }
+
+    fn check_instance(&self, expect_static: bool) -> Result<(), RuntimeError> {
+        match (self.is_static()?, expect_static) {
+            (true, false) => Err(RuntimeError::FieldIsStatic(self.get_name()?)),
+            (false, true) => Err(RuntimeError::FieldNotStatic(self.get_name()?)),
+            _ => Ok(()),
+        }
+    }
+
+    /// the runtime copies as many bytes as the field holds, so a smaller `T` would be overrun
+    fn check_size<T>(&self) -> Result<(), RuntimeError> {
+        let expected = self.get_type()?.get_size()?;
+        let got = mem::size_of::<T>();
+
+        if expected != got {
+            return Err(RuntimeError::SizeMismatch { expected, got });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! TODO
+
+use std::{fmt, marker::PhantomData};
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::object::ManagedObject;
+
+/// the kinds of gc handles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcHandleKind {
+    /// keeps the object alive
+    Strong,
+    /// doesn't keep the object alive, the handle is emptied once it's collected
+    Weak,
+    /// keeps the object alive and stops the gc from moving it
+    Pinned,
+}
+
+impl fmt::Display for GcHandleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GcHandleKind::Strong => write!(f, "strong"),
+            GcHandleKind::Weak => write!(f, "weak"),
+            GcHandleKind::Pinned => write!(f, "pinned"),
+        }
+    }
+}
+
+/// A handle keeping track of a managed object across frames
+///
+/// raw object pointers may be moved or collected by the gc as soon as rust is done with them,
+/// a handle always returns the current location of the object. the handle is freed when dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use unity_rs::common::{gc_handle::GcHandle, string::UnityString};
+///
+/// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+/// let name = GcHandle::strong(&UnityString::new("Player")?)?;
+///
+/// // frames later
+/// if let Some(name) = name.get()? {
+///     println!("{}", name.to_utf8()?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct GcHandle<T: ManagedObject> {
+    handle: usize,
+    kind: GcHandleKind,
+    pd: PhantomData<T>,
+}
+
+// the handle is only a number, the runtimes synchronize access to its target
+unsafe impl<T: ManagedObject> Send for GcHandle<T> {}
+unsafe impl<T: ManagedObject> Sync for GcHandle<T> {}
+
+impl<T: ManagedObject> GcHandle<T> {
+    /// creates a handle of `kind` to `object`
+    pub fn new(object: &T, kind: GcHandleKind) -> Result<GcHandle<T>, RuntimeError> {
+        let handle = get_runtime()?.new_gc_handle(&object.as_object(), kind)?;
+
+        Ok(GcHandle {
+            handle,
+            kind,
+            pd: PhantomData,
+        })
+    }
+
+    /// creates a handle keeping `object` alive
+    pub fn strong(object: &T) -> Result<GcHandle<T>, RuntimeError> {
+        GcHandle::new(object, GcHandleKind::Strong)
+    }
+
+    /// creates a handle that lets `object` be collected
+    pub fn weak(object: &T) -> Result<GcHandle<T>, RuntimeError> {
+        GcHandle::new(object, GcHandleKind::Weak)
+    }
+
+    /// creates a handle keeping `object` alive and in place
+    pub fn pinned(object: &T) -> Result<GcHandle<T>, RuntimeError> {
+        GcHandle::new(object, GcHandleKind::Pinned)
+    }
+
+    /// returns the object, `None` if the handle is weak and it was collected
+    ///
+    /// the returned pointer is only valid until the gc runs again, unless the handle is pinned
+    pub fn get(&self) -> Result<Option<T>, RuntimeError> {
+        Ok(get_runtime()?.get_gc_handle_target(self.handle)?.map(T::from_object))
+    }
+
+    /// the kind of the handle
+    pub fn kind(&self) -> GcHandleKind {
+        self.kind
+    }
+
+    /// the raw handle, as the runtime hands it out
+    pub fn as_raw(&self) -> usize {
+        self.handle
+    }
+}
+
+impl<T: ManagedObject> Drop for GcHandle<T> {
+    fn drop(&mut self) {
+        // nothing sensible to do with an error while dropping
+        if let Ok(runtime) = get_runtime() {
+            let _ = runtime.free_gc_handle(self.handle);
+        }
+    }
+}
+
+impl<T: ManagedObject> fmt::Debug for GcHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GcHandle")
+            .field("handle", &self.handle)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
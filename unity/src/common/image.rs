@@ -0,0 +1,40 @@
+//! TODO
+
+use std::ffi::c_void;
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::class::UnityClass;
+
+/// Represents a C# Image, the loaded contents of an Assembly
+#[derive(Debug)]
+pub struct UnityImage {
+    /// The inner pointer to the Image
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityImage {}
+unsafe impl Sync for UnityImage {}
+
+impl Clone for UnityImage {
+    fn clone(&self) -> UnityImage {
+        UnityImage { ..*self }
+    }
+}
+
+impl UnityImage {
+    /// returns the name of the image
+    pub fn name(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_image_name(self)
+    }
+
+    /// returns the file the image was loaded from
+    pub fn filename(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_image_filename(self)
+    }
+
+    /// looks up a class in this image
+    pub fn get_class(&self, namespace: &str, name: &str) -> Result<UnityClass, RuntimeError> {
+        get_runtime()?.get_class_from_image(self, namespace, name)
+    }
+}
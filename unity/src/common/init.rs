@@ -0,0 +1,84 @@
+//! TODO
+
+use std::{
+    mem,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{hook::Hook, libs::NativeMethod, runtime::RuntimeError};
+
+use super::{callback::catch, domain::UnityDomain};
+
+/// a callback run once the runtime has initialized, with its root domain
+pub type InitCallback = Box<dyn FnOnce(UnityDomain) + Send>;
+
+/// where the runtime is in its initialization
+struct InitState {
+    /// the hook on the init export, installed along with the first callback
+    hook: Option<Hook>,
+    /// callbacks waiting for the runtime to initialize
+    callbacks: Vec<InitCallback>,
+    /// the root domain, once the runtime has initialized
+    domain: Option<UnityDomain>,
+}
+
+static STATE: Mutex<InitState> = Mutex::new(InitState {
+    hook: None,
+    callbacks: Vec::new(),
+    domain: None,
+});
+
+fn state() -> MutexGuard<'static, InitState> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// queues `callback`, hooking the init export with `install` if that hasn't happened yet
+///
+/// before hooking, `current` is asked for the root domain, in case the runtime initialized before the hook could see it.
+/// if the runtime already initialized, `callback` runs right away.
+pub(crate) fn register(
+    callback: InitCallback,
+    current: impl FnOnce() -> Option<UnityDomain>,
+    install: impl FnOnce() -> Result<Hook, RuntimeError>,
+) -> Result<(), RuntimeError> {
+    let mut state = state();
+
+    // the init export never runs again, hooking it would leave the callbacks waiting forever
+    if state.domain.is_none() && state.hook.is_none() {
+        state.domain = current();
+    }
+
+    if let Some(domain) = state.domain.clone() {
+        // not holding the lock, the callback may register more
+        drop(state);
+        catch("init callback", move || callback(domain));
+        return Ok(());
+    }
+
+    if state.hook.is_none() {
+        state.hook = Some(install()?);
+    }
+
+    state.callbacks.push(callback);
+
+    Ok(())
+}
+
+/// the original init export, `T` being its `fn` type
+pub(crate) fn original<T>() -> Option<NativeMethod<T>> {
+    state().hook.as_ref().map(Hook::original)
+}
+
+/// runs all queued callbacks, called from the detours once the original init returned
+pub(crate) fn initialized(domain: UnityDomain) {
+    let callbacks = {
+        let mut state = state();
+        state.domain = Some(domain.clone());
+        mem::take(&mut state.callbacks)
+    };
+
+    for callback in callbacks {
+        let domain = domain.clone();
+        catch("init callback", move || callback(domain));
+    }
+}
@@ -0,0 +1,296 @@
+//! TODO
+
+use std::{ffi::c_void, ptr};
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::{method::MethodPointer, object::UnityObject, string::UnityString};
+
+/// A rust type an internal call can take as argument
+///
+/// # Safety
+///
+/// `Abi` must have the exact layout the runtime passes the managed argument with
+pub unsafe trait FromManaged: Sized {
+    /// the type the runtime passes
+    type Abi: Copy;
+
+    /// converts the argument the runtime passed
+    fn from_managed(abi: Self::Abi) -> Self;
+}
+
+/// A rust type an internal call can return
+///
+/// # Safety
+///
+/// `Abi` must have the exact layout the runtime expects the managed return value in
+pub unsafe trait IntoManaged {
+    /// the type the runtime expects
+    type Abi: Copy;
+
+    /// converts the value into what the runtime expects
+    fn into_managed(self) -> Self::Abi;
+
+    /// returned in place of a value when the internal call panicked, and the panic couldn't be raised
+    fn fallback() -> Self::Abi;
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {
+        $(
+            unsafe impl FromManaged for $ty {
+                type Abi = $ty;
+
+                fn from_managed(abi: $ty) -> $ty {
+                    abi
+                }
+            }
+
+            unsafe impl IntoManaged for $ty {
+                type Abi = $ty;
+
+                fn into_managed(self) -> $ty {
+                    self
+                }
+
+                fn fallback() -> $ty {
+                    <$ty>::default()
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64);
+
+// managed bools are a byte, which may hold more than 0 and 1
+unsafe impl FromManaged for bool {
+    type Abi = u8;
+
+    fn from_managed(abi: u8) -> bool {
+        abi != 0
+    }
+}
+
+unsafe impl IntoManaged for bool {
+    type Abi = u8;
+
+    fn into_managed(self) -> u8 {
+        self as u8
+    }
+
+    fn fallback() -> u8 {
+        0
+    }
+}
+
+unsafe impl IntoManaged for () {
+    type Abi = ();
+
+    fn into_managed(self) {}
+
+    fn fallback() {}
+}
+
+unsafe impl FromManaged for UnityObject {
+    type Abi = *mut c_void;
+
+    fn from_managed(abi: *mut c_void) -> UnityObject {
+        UnityObject { inner: abi }
+    }
+}
+
+unsafe impl IntoManaged for UnityObject {
+    type Abi = *mut c_void;
+
+    fn into_managed(self) -> *mut c_void {
+        self.inner
+    }
+
+    fn fallback() -> *mut c_void {
+        ptr::null_mut()
+    }
+}
+
+// null objects are passed as `None`
+unsafe impl FromManaged for Option<UnityObject> {
+    type Abi = *mut c_void;
+
+    fn from_managed(abi: *mut c_void) -> Option<UnityObject> {
+        match abi.is_null() {
+            true => None,
+            false => Some(UnityObject { inner: abi }),
+        }
+    }
+}
+
+unsafe impl IntoManaged for Option<UnityObject> {
+    type Abi = *mut c_void;
+
+    fn into_managed(self) -> *mut c_void {
+        self.map_or(ptr::null_mut(), |object| object.inner)
+    }
+
+    fn fallback() -> *mut c_void {
+        ptr::null_mut()
+    }
+}
+
+unsafe impl FromManaged for UnityString {
+    type Abi = *mut c_void;
+
+    fn from_managed(abi: *mut c_void) -> UnityString {
+        UnityString { inner: abi }
+    }
+}
+
+unsafe impl IntoManaged for UnityString {
+    type Abi = *mut c_void;
+
+    fn into_managed(self) -> *mut c_void {
+        self.inner
+    }
+
+    fn fallback() -> *mut c_void {
+        ptr::null_mut()
+    }
+}
+
+// copied out of the managed string, null becomes empty
+unsafe impl FromManaged for String {
+    type Abi = *mut c_void;
+
+    fn from_managed(abi: *mut c_void) -> String {
+        match abi.is_null() {
+            true => String::new(),
+            false => UnityString { inner: abi }.to_utf8().unwrap_or_default(),
+        }
+    }
+}
+
+// returned as a new managed string, or null if it can't be created
+unsafe impl IntoManaged for String {
+    type Abi = *mut c_void;
+
+    fn into_managed(self) -> *mut c_void {
+        UnityString::new(&self).map_or(ptr::null_mut(), |string| string.inner)
+    }
+
+    fn fallback() -> *mut c_void {
+        ptr::null_mut()
+    }
+}
+
+/// checks that `name` is in the `Namespace.Class::Method` format the runtimes expect
+///
+/// the namespace is optional, nested classes are separated with `/`,
+/// and the method may be followed by its parameter types, e.g. `Namespace.Class::Method(int,string)`.
+///
+/// # Errors
+///
+/// * `RuntimeError::EmptyString` - if `name` is empty
+/// * `RuntimeError::InvalidInternalCallName` - if `name` isn't in the expected format
+///
+/// # Examples
+///
+/// ```
+/// use unity_rs::common::internal_call::validate_name;
+///
+/// assert!(validate_name("UnityEngine.Debug::Log").is_ok());
+/// assert!(validate_name("UnityEngine.Debug::Log(object)").is_ok());
+/// assert!(validate_name("UnityEngine.Debug.Log").is_err());
+/// assert!(validate_name("UnityEngine.Debug::Log(object").is_err());
+/// ```
+pub fn validate_name(name: &str) -> Result<(), RuntimeError> {
+    if name.is_empty() {
+        return Err(RuntimeError::EmptyString);
+    }
+
+    let invalid = || RuntimeError::InvalidInternalCallName(name.to_string());
+
+    if name.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+
+    let (class, method) = name.split_once("::").ok_or_else(invalid)?;
+
+    let (method, params) = match method.split_once('(') {
+        Some((method, params)) => (method, Some(params)),
+        None => (method, None),
+    };
+
+    let class_valid = !class.is_empty() && class.split(['.', '/']).all(|part| !part.is_empty());
+    let method_valid = !method.is_empty() && !method.contains([':', ')']);
+    let params_valid = params.is_none_or(|params| params.strip_suffix(')').is_some_and(|params| !params.contains(['(', ')'])));
+
+    match class_valid && method_valid && params_valid {
+        true => Ok(()),
+        false => Err(invalid()),
+    }
+}
+
+/// registers `func` as the internal call `name` with the global runtime
+///
+/// prefer [`internal_call!`](crate::internal_call), which generates `func` with the right signature.
+///
+/// # Errors
+///
+/// * `RuntimeError::InvalidInternalCallName` - if `name` isn't in the `Namespace.Class::Method` format
+pub fn register(name: &str, func: MethodPointer) -> Result<(), RuntimeError> {
+    get_runtime()?.add_internal_call(name.to_string(), func)
+}
+
+/// Registers a rust function as an internal call
+///
+/// the function is wrapped in an `extern "C-unwind"` shim, which converts the arguments with [`FromManaged`]
+/// and the return value with [`IntoManaged`].
+/// a panic is raised as a managed exception in the caller, see [`catch_managed`](crate::common::callback::catch_managed),
+/// and [`IntoManaged::fallback`] returned if that fails.
+///
+/// evaluates to `Result<(), RuntimeError>`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use unity_rs::{common::string::UnityString, internal_call};
+///
+/// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+/// internal_call!("Game.Player::Heal", fn heal(amount: i32, reason: String) -> bool {
+///     println!("healing {} for {}", amount, reason);
+///     true
+/// })?;
+///
+/// internal_call!("Game.Player::Log(string)", fn log(message: UnityString) {
+///     if let Ok(message) = message.to_utf8() {
+///         println!("{}", message);
+///     }
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! internal_call {
+    ($name:expr, fn $func:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)? $body:block) => {{
+        fn $func($($arg: $ty),*) -> $crate::internal_call!(@ret $($ret)?) $body
+
+        // raising the panic as a managed exception unwinds through the shim
+        extern "C-unwind" fn shim(
+            $($arg: <$ty as $crate::common::internal_call::FromManaged>::Abi),*
+        ) -> <$crate::internal_call!(@ret $($ret)?) as $crate::common::internal_call::IntoManaged>::Abi {
+            let result = unsafe {
+                $crate::common::callback::catch_managed(stringify!($func), move || {
+                    $func($(<$ty as $crate::common::internal_call::FromManaged>::from_managed($arg)),*)
+                })
+            };
+
+            match result {
+                Some(value) => $crate::common::internal_call::IntoManaged::into_managed(value),
+                None => <$crate::internal_call!(@ret $($ret)?) as $crate::common::internal_call::IntoManaged>::fallback(),
+            }
+        }
+
+        $crate::common::internal_call::register($name, shim as *mut ::std::ffi::c_void)
+    }};
+    (@ret) => { () };
+    (@ret $ret:ty) => { $ret };
+}
@@ -1,3 +1,361 @@
+//! TODO
+
 use std::ffi::c_void;
 
-pub type MethodPointer = *mut c_void;
\ No newline at end of file
+use crate::{
+    hook::{Hook, HookGuard},
+    runtime::{get_runtime, LookupKind, RuntimeError},
+};
+
+use super::{class::UnityClass, object::UnityObject, ty::UnityType, value::ManagedValue};
+
+pub type MethodPointer = *mut c_void;
+
+/// `METHOD_ATTRIBUTE_STATIC` from ECMA-335
+const METHOD_ATTRIBUTE_STATIC: i32 = 0x0010;
+
+/// Represents a C# Method
+#[derive(Debug)]
+pub struct UnityMethod {
+    /// The inner pointer to the Method
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityMethod {}
+unsafe impl Sync for UnityMethod {}
+
+impl Clone for UnityMethod {
+    fn clone(&self) -> UnityMethod {
+        UnityMethod { ..*self }
+    }
+}
+
+impl UnityMethod {
+    /// looks up a method on a class
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - the class declaring the method
+    /// * `name` - the name of the method
+    /// * `param_count` - the amount of parameters, or -1 to take the first method with that name
+    pub fn get(class: &UnityClass, name: &str, param_count: i32) -> Result<UnityMethod, RuntimeError> {
+        get_runtime()?.get_method(class, name, param_count)
+    }
+
+    /// looks up the overload of a method with exactly the given parameter types
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - the class declaring the method
+    /// * `name` - the name of the method
+    /// * `param_types` - the parameter types, either full names like `UnityEngine.Vector3`
+    ///   or without the namespace like `Vector3`, also in generic arguments like `List<Vector3>`.
+    ///   nested types may use `+`, and `ref` parameters end in `&`
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::NotFound` - if the class declares no method called `name`
+    /// * `RuntimeError::NoMatchingOverload` - if no overload takes `param_types`, listing the ones there are
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::class::UnityClass;
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("UnityEngine.CoreModule", "UnityEngine", "Mathf")?;
+    /// let max = class.find_method("Max", &["Single", "Single"])?;
+    ///
+    /// assert_eq!(max.get_signature()?, "System.Single Max(System.Single a, System.Single b)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find(class: &UnityClass, name: &str, param_types: &[&str]) -> Result<UnityMethod, RuntimeError> {
+        let mut candidates = Vec::new();
+
+        for method in class.methods()? {
+            let method = method?;
+
+            if method.get_name()? != name {
+                continue;
+            }
+
+            let types = method.get_param_types()?;
+
+            if types.len() == param_types.len() {
+                let mut matches = true;
+
+                for (ty, wanted) in types.iter().zip(param_types) {
+                    if !type_name_matches(&ty.get_name()?, wanted) {
+                        matches = false;
+                        break;
+                    }
+                }
+
+                if matches {
+                    return Ok(method);
+                }
+            }
+
+            candidates.push(method.get_signature()?);
+        }
+
+        if candidates.is_empty() {
+            return Err(RuntimeError::not_found(LookupKind::Method, format!("{}::{}", class.get_name()?, name)));
+        }
+
+        Err(RuntimeError::NoMatchingOverload {
+            name: format!("{}::{}({})", class.get_name()?, name, param_types.join(", ")),
+            candidates,
+        })
+    }
+
+    /// returns the name of the method
+    pub fn get_name(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_method_name(self)
+    }
+
+    /// returns the class declaring the method
+    pub fn get_class(&self) -> Result<UnityClass, RuntimeError> {
+        get_runtime()?.get_method_class(self)
+    }
+
+    /// returns the attribute flags of the method
+    pub fn get_flags(&self) -> Result<i32, RuntimeError> {
+        get_runtime()?.get_method_flags(self)
+    }
+
+    /// whether the method is static
+    pub fn is_static(&self) -> Result<bool, RuntimeError> {
+        Ok(self.get_flags()? & METHOD_ATTRIBUTE_STATIC != 0)
+    }
+
+    /// returns the amount of parameters the method takes
+    pub fn get_param_count(&self) -> Result<u32, RuntimeError> {
+        get_runtime()?.get_method_param_count(self)
+    }
+
+    /// returns the return type of the method, `System.Void` if it returns nothing
+    pub fn get_return_type(&self) -> Result<UnityType, RuntimeError> {
+        get_runtime()?.get_method_return_type(self)
+    }
+
+    /// returns the types of the parameters, in order
+    pub fn get_param_types(&self) -> Result<Vec<UnityType>, RuntimeError> {
+        get_runtime()?.get_method_param_types(self)
+    }
+
+    /// returns the names of the parameters, in order
+    pub fn get_param_names(&self) -> Result<Vec<String>, RuntimeError> {
+        get_runtime()?.get_method_param_names(self)
+    }
+
+    /// returns the signature of the method, like `System.Single Max(System.Single a, System.Single b)`
+    pub fn get_signature(&self) -> Result<String, RuntimeError> {
+        let runtime = get_runtime()?;
+
+        let types = runtime.get_method_param_types(self)?;
+        let names = runtime.get_method_param_names(self)?;
+
+        let mut params = Vec::with_capacity(types.len());
+        for (ty, name) in types.iter().zip(names) {
+            params.push(format!("{} {}", ty.get_name()?, name));
+        }
+
+        Ok(format!(
+            "{} {}({})",
+            runtime.get_method_return_type(self)?.get_name()?,
+            runtime.get_method_name(self)?,
+            params.join(", ")
+        ))
+    }
+
+    /// invokes the method
+    ///
+    /// value types are passed by pointer, reference types by their object pointer.
+    /// returns the (boxed, for value types) return value, or `None` for void methods.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - the object to invoke the method on, `None` for static methods
+    /// * `args` - the arguments, these have to match the parameters of the method
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::ArgumentCount` - if the amount of arguments doesn't match
+    /// * `RuntimeError::ManagedException` - if the method threw an exception
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::{class::UnityClass, string::UnityString};
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("UnityEngine.CoreModule", "UnityEngine", "Debug")?;
+    /// let log = class.get_method("Log", 1)?;
+    ///
+    /// let mut message = UnityString::new("Hello from rust!")?;
+    /// log.invoke(None, &mut [&mut message])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn invoke(
+        &self,
+        instance: Option<&UnityObject>,
+        args: &mut [&mut dyn ManagedValue],
+    ) -> Result<Option<UnityObject>, RuntimeError> {
+        let runtime = get_runtime()?;
+
+        let expected = runtime.get_method_param_count(self)?;
+        if expected as usize != args.len() {
+            return Err(RuntimeError::ArgumentCount {
+                expected,
+                got: args.len(),
+            });
+        }
+
+        let mut args: Vec<*mut c_void> = args.iter_mut().map(|arg| arg.as_arg()).collect();
+
+        runtime.invoke_method(self, instance, &mut args)
+    }
+
+    /// returns the native code of the method, compiling it first on mono
+    pub fn get_pointer(&self) -> Result<MethodPointer, RuntimeError> {
+        get_runtime()?.get_method_pointer(self)
+    }
+
+    /// redirects every call of the method to `detour`, until the returned guard is dropped
+    ///
+    /// `T` is the `fn` type of the native code. instance methods take the instance first,
+    /// and on il2cpp every method takes its `MethodInfo` as trailing parameter.
+    ///
+    /// # Safety
+    ///
+    /// see [`Hook::install`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{ffi::c_void, sync::OnceLock};
+    ///
+    /// use unity_rs::{common::class::UnityClass, hook::HookGuard};
+    ///
+    /// type TakeDamage = extern "C" fn(*mut c_void, f32, *mut c_void);
+    ///
+    /// static HOOK: OnceLock<HookGuard<TakeDamage>> = OnceLock::new();
+    ///
+    /// extern "C" fn take_damage(this: *mut c_void, _amount: f32, method: *mut c_void) {
+    ///     HOOK.get().unwrap().original()(this, 0.0, method)
+    /// }
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("Assembly-CSharp", "", "Player")?;
+    /// let method = class.get_method("TakeDamage", 1)?;
+    ///
+    /// let hook = unsafe { method.hook::<TakeDamage>(take_damage as *mut c_void)? };
+    /// HOOK.set(hook).unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn hook<T>(&self, detour: MethodPointer) -> Result<HookGuard<T>, RuntimeError> {
+        let hook = Hook::install(self.get_pointer()?, detour)?;
+
+        Ok(HookGuard::new(hook))
+    }
+}
+
+/// splits a type name into names and the punctuation between them, skipping whitespace
+fn type_name_tokens(name: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (index, c) in name.char_indices() {
+        if matches!(c, '<' | '>' | '[' | ']' | ',' | '&' | '*') || c.is_whitespace() {
+            if let Some(start) = start.take() {
+                tokens.push(&name[start..index]);
+            }
+
+            if !c.is_whitespace() {
+                tokens.push(&name[index..index + c.len_utf8()]);
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+
+    if let Some(start) = start {
+        tokens.push(&name[start..]);
+    }
+
+    tokens
+}
+
+/// whether the full type name `name` is `wanted`, which may leave out namespaces and declaring types,
+/// also in generic arguments
+///
+/// nested types may be separated by `.`, `+` or `/`, and generic arity suffixes like `` `1 `` are ignored.
+fn type_name_matches(name: &str, wanted: &str) -> bool {
+    let (name, wanted) = (type_name_tokens(name), type_name_tokens(wanted));
+
+    name.len() == wanted.len()
+        && name.iter().zip(&wanted).all(|(name, wanted)| {
+            let mut segments = name.rsplit(['.', '+', '/']).map(|segment| segment.split('`').next().unwrap_or(segment));
+
+            wanted.rsplit(['.', '+', '/']).all(|wanted| segments.next() == Some(wanted.split('`').next().unwrap_or(wanted)))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::type_name_matches;
+
+    #[test]
+    fn namespaces_can_be_left_out() {
+        assert!(type_name_matches("UnityEngine.Vector3", "UnityEngine.Vector3"));
+        assert!(type_name_matches("UnityEngine.Vector3", "Vector3"));
+        assert!(type_name_matches("System.Int32", "Int32"));
+        assert!(!type_name_matches("UnityEngine.Vector3", "Vector2"));
+        assert!(!type_name_matches("UnityEngine.Vector3", "Engine.Vector3"));
+        assert!(!type_name_matches("Vector3", "UnityEngine.Vector3"));
+    }
+
+    #[test]
+    fn nested_types_match_any_separator() {
+        assert!(type_name_matches("Game.Player+Stats", "Game.Player+Stats"));
+        assert!(type_name_matches("Game.Player+Stats", "Player+Stats"));
+        assert!(type_name_matches("Game.Player+Stats", "Stats"));
+        assert!(type_name_matches("Game.Player/Stats", "Player+Stats"));
+        assert!(type_name_matches("Game.Player.Stats", "Player+Stats"));
+        assert!(!type_name_matches("Game.Player+Stats", "Enemy+Stats"));
+    }
+
+    #[test]
+    fn generic_arguments_drop_namespaces_too() {
+        let name = "System.Collections.Generic.List<UnityEngine.Vector3>";
+
+        assert!(type_name_matches(name, name));
+        assert!(type_name_matches(name, "List<Vector3>"));
+        assert!(type_name_matches(name, "List<UnityEngine.Vector3>"));
+        assert!(type_name_matches("System.Collections.Generic.List`1<UnityEngine.Vector3>", "List<Vector3>"));
+        assert!(type_name_matches(
+            "System.Collections.Generic.Dictionary<System.String,System.Collections.Generic.List<System.Int32>>",
+            "Dictionary<String, List<Int32>>"
+        ));
+        assert!(!type_name_matches(name, "List<Vector2>"));
+        assert!(!type_name_matches(name, "List"));
+        assert!(!type_name_matches(name, "HashSet<Vector3>"));
+    }
+
+    #[test]
+    fn arrays_pointers_and_references_must_match() {
+        assert!(type_name_matches("System.Int32[]", "Int32[]"));
+        assert!(type_name_matches("System.Single[,]", "Single[,]"));
+        assert!(type_name_matches("UnityEngine.Vector3&", "Vector3&"));
+        assert!(type_name_matches("System.Byte*", "Byte*"));
+        assert!(type_name_matches("System.Collections.Generic.List<System.Int32>[]", "List<Int32>[]"));
+        assert!(!type_name_matches("System.Int32[]", "Int32"));
+        assert!(!type_name_matches("System.Int32", "Int32[]"));
+        assert!(!type_name_matches("UnityEngine.Vector3&", "Vector3"));
+        assert!(!type_name_matches("System.Single[,]", "Single[]"));
+    }
+}
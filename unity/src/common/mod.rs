@@ -1,5 +1,20 @@
 //! TODO
 
+pub mod array;
+pub mod assembly;
+pub mod callback;
+pub mod class;
 pub mod domain;
+pub mod exception;
+pub mod field;
+pub mod gc_handle;
+pub mod image;
+pub mod init;
+pub mod internal_call;
 pub mod thread;
 pub mod method;
+pub mod object;
+pub mod property;
+pub mod string;
+pub mod ty;
+pub mod value;
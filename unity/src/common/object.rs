@@ -0,0 +1,198 @@
+//! TODO
+
+use std::{ffi::c_void, mem, ptr};
+
+use crate::runtime::{get_runtime, LookupKind, Runtime, RuntimeError};
+
+use super::{class::UnityClass, string::UnityString, value::ManagedValue};
+
+/// Represents a C# Object
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct UnityObject {
+    /// The inner pointer to the Object
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityObject {}
+unsafe impl Sync for UnityObject {}
+
+impl Clone for UnityObject {
+    fn clone(&self) -> UnityObject {
+        UnityObject { ..*self }
+    }
+}
+
+impl UnityObject {
+    /// creates an instance of `class` and runs its parameterless constructor
+    ///
+    /// value types without a constructor are left zeroed, like `default` in C#.
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::NotFound` - if a reference type has no parameterless constructor
+    /// * `RuntimeError::ManagedException` - if the constructor throws
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::{class::UnityClass, object::UnityObject};
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("mscorlib", "System.Text", "StringBuilder")?;
+    /// let builder = UnityObject::new(&class)?;
+    ///
+    /// assert!(builder.is_instance_of(&class)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(class: &UnityClass) -> Result<UnityObject, RuntimeError> {
+        let runtime = get_runtime()?;
+
+        // il2cpp also finds the constructors of parent classes, which would leave this one's fields unset
+        let constructor = match runtime.get_method(class, ".ctor", 0) {
+            Ok(constructor) if runtime.get_method_class(&constructor)?.inner == class.inner => Some(constructor),
+            Ok(_) | Err(RuntimeError::NotFound { .. }) => None,
+            Err(error) => return Err(error),
+        };
+
+        match constructor {
+            Some(constructor) => {
+                let object = runtime.new_object(class)?;
+                runtime.invoke_method(&constructor, Some(&object), &mut [])?;
+                Ok(object)
+            }
+            None if runtime.is_value_type(class)? => runtime.new_object(class),
+            None => Err(RuntimeError::not_found(LookupKind::Method, format!("{}::.ctor()", runtime.get_class_name(class)?))),
+        }
+    }
+
+    /// creates an instance of `class` without running any constructor, all fields zeroed
+    pub fn allocate(class: &UnityClass) -> Result<UnityObject, RuntimeError> {
+        get_runtime()?.new_object(class)
+    }
+
+    /// boxes `value` as an instance of the value type `class`
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::NotValueType` - if `class` is a reference type
+    /// * `RuntimeError::SizeMismatch` - if `T` doesn't have the size of `class`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::{class::UnityClass, object::UnityObject};
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("mscorlib", "System", "Int32")?;
+    /// let boxed = UnityObject::box_value(&class, 42i32)?;
+    ///
+    /// assert_eq!(boxed.unbox::<i32>()?, 42);
+    /// assert_eq!(boxed.to_string()?, "42");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn box_value<T: ManagedValue>(class: &UnityClass, mut value: T) -> Result<UnityObject, RuntimeError> {
+        let runtime = get_runtime()?;
+        check_value_type::<T>(runtime, class)?;
+
+        runtime.box_value(class, (&mut value as *mut T).cast())
+    }
+
+    /// copies the value out of a boxed value type
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::NotValueType` - if the object isn't a boxed value type
+    /// * `RuntimeError::SizeMismatch` - if `T` doesn't have the size of the value
+    pub fn unbox<T: ManagedValue>(&self) -> Result<T, RuntimeError> {
+        let runtime = get_runtime()?;
+        check_value_type::<T>(runtime, &runtime.get_object_class(self)?)?;
+
+        let value = runtime.unbox_value(self)?;
+
+        Ok(unsafe { ptr::read_unaligned(value.cast()) })
+    }
+
+    /// returns the class of the object
+    pub fn class(&self) -> Result<UnityClass, RuntimeError> {
+        get_runtime()?.get_object_class(self)
+    }
+
+    /// returns whether the object is an instance of `class`, or of a class deriving from or implementing it
+    pub fn is_instance_of(&self, class: &UnityClass) -> Result<bool, RuntimeError> {
+        get_runtime()?.is_instance_of(self, class)
+    }
+
+    /// calls `ToString` on the object
+    pub fn to_string(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.object_to_string(self)
+    }
+}
+
+/// checks that `class` is a value type `T` can hold
+fn check_value_type<T>(runtime: &dyn Runtime, class: &UnityClass) -> Result<(), RuntimeError> {
+    if !runtime.is_value_type(class)? {
+        return Err(RuntimeError::NotValueType(runtime.get_class_name(class)?));
+    }
+
+    let size = runtime.get_class_value_size(class)?;
+
+    if size != mem::size_of::<T>() {
+        return Err(RuntimeError::SizeMismatch {
+            expected: size,
+            got: mem::size_of::<T>(),
+        });
+    }
+
+    Ok(())
+}
+
+/// calls `ToString` on `object` like any other virtual method, for runtimes without a dedicated export
+pub(crate) fn invoke_to_string(runtime: &dyn Runtime, object: &UnityObject) -> Result<String, RuntimeError> {
+    let object_class = runtime.get_class_from_image(&runtime.get_corlib()?, "System", "Object")?;
+    let method = runtime.get_virtual_method(object, &runtime.get_method(&object_class, "ToString", 0)?)?;
+
+    match runtime.invoke_method(&method, Some(object), &mut [])? {
+        Some(string) => runtime.string_to_utf8(&UnityString { inner: string.inner }),
+        None => Err(RuntimeError::ReturnedNull("ToString")),
+    }
+}
+
+/// A rust type wrapping a reference to a managed object
+///
+/// # Safety
+///
+/// the type must be nothing but the object pointer
+pub unsafe trait ManagedObject: Sized {
+    /// wraps an object pointer
+    fn from_object(object: UnityObject) -> Self;
+
+    /// the wrapped object
+    fn as_object(&self) -> UnityObject;
+}
+
+unsafe impl ManagedObject for UnityObject {
+    fn from_object(object: UnityObject) -> UnityObject {
+        object
+    }
+
+    fn as_object(&self) -> UnityObject {
+        self.clone()
+    }
+}
+
+unsafe impl ManagedObject for UnityString {
+    fn from_object(object: UnityObject) -> UnityString {
+        UnityString {
+            inner: object.inner,
+        }
+    }
+
+    fn as_object(&self) -> UnityObject {
+        UnityObject {
+            inner: self.inner,
+        }
+    }
+}
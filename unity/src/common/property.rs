@@ -0,0 +1,86 @@
+//! TODO
+
+use std::ffi::c_void;
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::{class::UnityClass, method::UnityMethod, object::UnityObject, value::ManagedValue};
+
+/// Represents a C# Property
+#[derive(Debug)]
+pub struct UnityProperty {
+    /// The inner pointer to the Property
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityProperty {}
+unsafe impl Sync for UnityProperty {}
+
+impl Clone for UnityProperty {
+    fn clone(&self) -> UnityProperty {
+        UnityProperty { ..*self }
+    }
+}
+
+impl UnityProperty {
+    /// looks up a property on a class
+    pub fn find(class: &UnityClass, name: &str) -> Result<UnityProperty, RuntimeError> {
+        get_runtime()?.get_property(class, name)
+    }
+
+    /// returns the name of the property
+    pub fn get_name(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_property_name(self)
+    }
+
+    /// returns the `get_` method of the property
+    pub fn get_getter(&self) -> Result<UnityMethod, RuntimeError> {
+        match get_runtime()?.get_property_get_method(self)? {
+            Some(method) => Ok(method),
+            None => Err(RuntimeError::NoGetter(self.get_name()?)),
+        }
+    }
+
+    /// returns the `set_` method of the property
+    pub fn get_setter(&self) -> Result<UnityMethod, RuntimeError> {
+        match get_runtime()?.get_property_set_method(self)? {
+            Some(method) => Ok(method),
+            None => Err(RuntimeError::NoSetter(self.get_name()?)),
+        }
+    }
+
+    /// reads the property by invoking its getter
+    ///
+    /// returns the value the same way [`UnityMethod::invoke`] does, boxed for value types.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - the object to read the property of, `None` for static properties
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::class::UnityClass;
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("UnityEngine.CoreModule", "UnityEngine", "Application")?;
+    /// let version = class.get_property("unityVersion")?.get(None)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, instance: Option<&UnityObject>) -> Result<Option<UnityObject>, RuntimeError> {
+        self.get_getter()?.invoke(instance, &mut [])
+    }
+
+    /// writes the property by invoking its setter
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - the object to write the property of, `None` for static properties
+    /// * `value` - the new value, passed like a method argument
+    pub fn set<T: ManagedValue>(&self, instance: Option<&UnityObject>, mut value: T) -> Result<(), RuntimeError> {
+        self.get_setter()?.invoke(instance, &mut [&mut value])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! TODO
+
+use std::{
+    ffi::c_void,
+    fmt::{self, Display},
+};
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+/// Represents a C# String
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct UnityString {
+    /// The inner pointer to the String
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityString {}
+unsafe impl Sync for UnityString {}
+
+impl Clone for UnityString {
+    fn clone(&self) -> UnityString {
+        UnityString { ..*self }
+    }
+}
+
+impl UnityString {
+    /// creates a new managed string in the root domain
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::string::UnityString;
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let string = UnityString::new("Hello from rust!")?;
+    /// assert_eq!(string.to_utf8()?, "Hello from rust!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(value: &str) -> Result<UnityString, RuntimeError> {
+        get_runtime()?.new_string(value)
+    }
+
+    /// creates a new managed string from UTF-16 code units
+    pub fn from_utf16(value: &[u16]) -> Result<UnityString, RuntimeError> {
+        get_runtime()?.new_string_utf16(value)
+    }
+
+    /// reads the string back into a rust String
+    pub fn to_utf8(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.string_to_utf8(self)
+    }
+
+    /// copies the UTF-16 code units of the string
+    pub fn to_utf16(&self) -> Result<Vec<u16>, RuntimeError> {
+        get_runtime()?.string_to_utf16(self)
+    }
+}
+
+impl Display for UnityString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_utf8().map_err(|_| fmt::Error)?;
+        write!(f, "{}", value)
+    }
+}
+
+/// # Panics
+///
+/// panics if no runtime is present, or the runtime fails to create the string.
+/// use [`UnityString::new`] if you need to handle that.
+impl From<&str> for UnityString {
+    fn from(value: &str) -> UnityString {
+        UnityString::new(value).expect("failed to create managed string")
+    }
+}
+
+impl TryFrom<UnityString> for String {
+    type Error = RuntimeError;
+
+    fn try_from(value: UnityString) -> Result<String, RuntimeError> {
+        value.to_utf8()
+    }
+}
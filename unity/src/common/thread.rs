@@ -1,6 +1,23 @@
 //! TODO
 
-use std::ffi::c_void;
+use std::{
+    collections::VecDeque,
+    ffi::c_void,
+    future::Future,
+    marker::PhantomData,
+    mem,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    hook::{Hook, HookError},
+    internal_call,
+    runtime::{get_runtime, Runtime, RuntimeError, RuntimeType},
+};
+
+use super::{callback, method::{MethodPointer, UnityMethod}};
 
 /// Represents a C# Thread
 #[derive(Debug)]
@@ -17,3 +34,397 @@ impl Clone for UnityThread {
         UnityThread { ..*self }
     }
 }
+
+/// Keeps the current thread attached to the runtime
+///
+/// detaches the thread when dropped, unless it was already attached before the guard was created.
+/// the guard can't leave the thread, since only the attached thread can detach itself.
+pub struct AttachedThread<'a> {
+    runtime: &'a dyn Runtime,
+    thread: UnityThread,
+    /// whether the guard attached the thread, and so has to detach it
+    attached: bool,
+    pd: PhantomData<*mut c_void>,
+}
+
+impl<'a> AttachedThread<'a> {
+    /// attaches the current thread to the root domain, if it isn't attached yet
+    pub fn new(runtime: &'a dyn Runtime) -> Result<AttachedThread<'a>, RuntimeError> {
+        let (thread, attached) = match runtime.get_current_thread() {
+            Ok(thread) => (thread, false),
+            Err(_) => (runtime.attach_to_thread(runtime.get_domain()?)?, true),
+        };
+
+        Ok(AttachedThread {
+            runtime,
+            thread,
+            attached,
+            pd: PhantomData,
+        })
+    }
+
+    /// the current thread
+    pub fn thread(&self) -> &UnityThread {
+        &self.thread
+    }
+
+    /// whether the thread is detached when the guard is dropped
+    pub fn is_owned(&self) -> bool {
+        self.attached
+    }
+}
+
+impl Drop for AttachedThread<'_> {
+    fn drop(&mut self) {
+        if self.attached {
+            // nothing sensible to do with an error while dropping
+            let _ = self.runtime.detach_from_thread(self.thread.clone());
+        }
+    }
+}
+
+impl std::fmt::Debug for AttachedThread<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttachedThread")
+            .field("thread", &self.thread)
+            .field("attached", &self.attached)
+            .finish()
+    }
+}
+
+impl dyn Runtime + '_ {
+    /// attaches the current thread to the root domain until the returned guard is dropped, see [`AttachedThread`]
+    pub fn attach_current_thread(&self) -> Result<AttachedThread<'_>, RuntimeError> {
+        AttachedThread::new(self)
+    }
+
+    /// runs `f` with the current thread attached to the root domain
+    ///
+    /// threads that weren't attached before are detached again afterwards,
+    /// so any rust thread can call into managed code with this.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::thread;
+    ///
+    /// use unity_rs::{common::class::UnityClass, runtime::get_runtime};
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// thread::spawn(|| {
+    ///     get_runtime()?.with_attached(|| {
+    ///         let class = UnityClass::get("UnityEngine.CoreModule", "UnityEngine", "Application")?;
+    ///         class.get_property("unityVersion")?.get(None)
+    ///     })?
+    /// })
+    /// .join()
+    /// .unwrap()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_attached<R>(&self, f: impl FnOnce() -> R) -> Result<R, RuntimeError> {
+        let _thread = self.attach_current_thread()?;
+
+        Ok(f())
+    }
+}
+
+/// work queued for a [`Dispatcher`]
+type Job = Box<dyn FnOnce() + Send>;
+
+/// the dispatcher pumped on unity's main thread
+static MAIN_DISPATCHER: Dispatcher = Dispatcher::new();
+
+/// the hook pumping the main dispatcher, see [`Dispatcher::pump_on`]
+static PUMP_HOOK: Mutex<Option<Hook>> = Mutex::new(None);
+
+/// A queue of work for one thread
+///
+/// any thread can queue work, which runs the next time the owning thread calls [`Dispatcher::pump`].
+/// [`Dispatcher::main`] is the one for unity's main thread, which most unity apis have to be called from.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use unity_rs::common::thread::Dispatcher;
+///
+/// static DISPATCHER: Dispatcher = Dispatcher::new();
+///
+/// let pumping = thread::current().id();
+/// let worker = thread::spawn(move || DISPATCHER.invoke(move || thread::current().id() == pumping).wait());
+///
+/// while !worker.is_finished() {
+///     DISPATCHER.pump();
+/// }
+///
+/// assert!(worker.join().unwrap().unwrap());
+/// ```
+pub struct Dispatcher {
+    queue: Mutex<VecDeque<Job>>,
+}
+
+impl Dispatcher {
+    /// creates an empty dispatcher
+    pub const fn new() -> Dispatcher {
+        Dispatcher {
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// the dispatcher for unity's main thread
+    ///
+    /// it has to be pumped every frame, either by [`Dispatcher::pump_on`] a per-frame method,
+    /// or from managed code through [`Dispatcher::register_pump`].
+    pub fn main() -> &'static Dispatcher {
+        &MAIN_DISPATCHER
+    }
+
+    fn queue(&self) -> MutexGuard<'_, VecDeque<Job>> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// queues `f`, without waiting for it to run
+    pub fn post(&self, f: impl FnOnce() + Send + 'static) {
+        self.queue().push_back(Box::new(f));
+    }
+
+    /// queues `f`, returning a future resolving to its result
+    ///
+    /// the future resolves to `RuntimeError::DispatchPanicked` if `f` panics.
+    /// threads that don't use async can block on it with [`Invoke::wait`], as long as it's not the pumping thread.
+    pub fn invoke<R: Send + 'static>(&self, f: impl FnOnce() -> R + Send + 'static) -> Invoke<R> {
+        let shared = Arc::new(Shared {
+            slot: Mutex::new(Slot {
+                result: None,
+                waker: None,
+            }),
+            done: Condvar::new(),
+        });
+
+        let sender = shared.clone();
+        self.post(move || {
+            let result = callback::catch("dispatched work", f).ok_or(RuntimeError::DispatchPanicked);
+            sender.complete(result);
+        });
+
+        Invoke { shared }
+    }
+
+    /// runs all queued work on the current thread, returning how much ran
+    ///
+    /// work queued while pumping runs on the next pump.
+    pub fn pump(&self) -> usize {
+        let jobs = mem::take(&mut *self.queue());
+        let count = jobs.len();
+
+        for job in jobs {
+            callback::catch("dispatched work", job);
+        }
+
+        count
+    }
+
+    /// how much work is waiting for the next pump
+    pub fn len(&self) -> usize {
+        self.queue().len()
+    }
+
+    /// whether no work is waiting for the next pump
+    pub fn is_empty(&self) -> bool {
+        self.queue().is_empty()
+    }
+
+    /// pumps the main dispatcher every time `method` is called
+    ///
+    /// `method` has to return nothing and take no arguments, like `Update` of a `MonoBehaviour`,
+    /// or a static method unity calls each frame. only one method can pump the main dispatcher.
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::ArgumentCount` - if `method` takes arguments
+    /// * `RuntimeError::UnsupportedSignature` - if `method` returns something
+    /// * `HookError::AlreadyHooked` - if another method already pumps the main dispatcher
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::common::{class::UnityClass, thread::Dispatcher};
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let class = UnityClass::get("Assembly-CSharp", "", "GameManager")?;
+    /// Dispatcher::pump_on(&class.get_method("Update", 0)?)?;
+    ///
+    /// Dispatcher::main().post(|| println!("on the main thread"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pump_on(method: &UnityMethod) -> Result<(), RuntimeError> {
+        let param_count = method.get_param_count()?;
+
+        if param_count != 0 {
+            return Err(RuntimeError::ArgumentCount {
+                expected: 0,
+                got: param_count as usize,
+            });
+        }
+
+        if method.get_return_type()?.get_name()? != "System.Void" {
+            return Err(RuntimeError::UnsupportedSignature(method.get_signature()?));
+        }
+
+        // instance methods take `this`, and il2cpp passes the method info after the arguments
+        let detour = match (get_runtime()?.get_type(), method.is_static()?) {
+            (RuntimeType::Mono(_), true) => pump_static as MethodPointer,
+            (RuntimeType::Mono(_), false) => pump_instance as MethodPointer,
+            (RuntimeType::Il2Cpp(_), true) => pump_static_il2cpp as MethodPointer,
+            (RuntimeType::Il2Cpp(_), false) => pump_instance_il2cpp as MethodPointer,
+        };
+
+        let mut hook = PUMP_HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(hook) = hook.as_ref() {
+            return Err(HookError::AlreadyHooked(hook.target() as usize).into());
+        }
+
+        *hook = Some(unsafe { Hook::install(method.get_pointer()?, detour) }?);
+
+        Ok(())
+    }
+
+    /// registers an internal call that pumps the main dispatcher, for managed code to call every frame
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the internal call, in the `Namespace.Class::Method` format
+    pub fn register_pump(name: &str) -> Result<(), RuntimeError> {
+        internal_call!(name, fn pump_main() {
+            Dispatcher::main().pump();
+        })
+    }
+}
+
+impl std::fmt::Debug for Dispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dispatcher").field("queued", &self.len()).finish()
+    }
+}
+
+impl Default for Dispatcher {
+    fn default() -> Dispatcher {
+        Dispatcher::new()
+    }
+}
+
+/// pumps the main dispatcher, then calls the method passed to [`Dispatcher::pump_on`]
+///
+/// `T` has to be the `fn` type of that method, `call` passes its arguments on.
+fn pump_then<T>(call: impl FnOnce(&T)) {
+    Dispatcher::main().pump();
+
+    let original = PUMP_HOOK.lock().unwrap_or_else(PoisonError::into_inner).as_ref().map(Hook::original::<T>);
+
+    if let Some(original) = original {
+        call(&original);
+    }
+}
+
+/// stands in for a static method on mono
+extern "C" fn pump_static() {
+    pump_then::<extern "C" fn()>(|original| original());
+}
+
+/// stands in for an instance method on mono
+extern "C" fn pump_instance(this: *mut c_void) {
+    pump_then::<extern "C" fn(*mut c_void)>(|original| original(this));
+}
+
+/// stands in for a static method on il2cpp
+extern "C" fn pump_static_il2cpp(method: *mut c_void) {
+    pump_then::<extern "C" fn(*mut c_void)>(|original| original(method));
+}
+
+/// stands in for an instance method on il2cpp
+extern "C" fn pump_instance_il2cpp(this: *mut c_void, method: *mut c_void) {
+    pump_then::<extern "C" fn(*mut c_void, *mut c_void)>(|original| original(this, method));
+}
+
+/// the result of an [`Invoke`], shared with the queued work
+struct Shared<R> {
+    slot: Mutex<Slot<R>>,
+    done: Condvar,
+}
+
+struct Slot<R> {
+    result: Option<Result<R, RuntimeError>>,
+    waker: Option<Waker>,
+}
+
+impl<R> Shared<R> {
+    fn slot(&self) -> MutexGuard<'_, Slot<R>> {
+        self.slot.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn complete(&self, result: Result<R, RuntimeError>) {
+        let waker = {
+            let mut slot = self.slot();
+            slot.result = Some(result);
+            slot.waker.take()
+        };
+
+        self.done.notify_all();
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// The result of work queued with [`Dispatcher::invoke`]
+///
+/// resolves once the work ran, either by awaiting it or with [`Invoke::wait`].
+pub struct Invoke<R> {
+    shared: Arc<Shared<R>>,
+}
+
+impl<R> Invoke<R> {
+    /// blocks until the work ran, returning its result
+    ///
+    /// never returns if called on the thread that's supposed to pump the dispatcher.
+    pub fn wait(self) -> Result<R, RuntimeError> {
+        let mut slot = self.shared.slot();
+
+        loop {
+            if let Some(result) = slot.result.take() {
+                return result;
+            }
+
+            slot = self.shared.done.wait(slot).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+impl<R> Future for Invoke<R> {
+    type Output = Result<R, RuntimeError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.shared.slot();
+
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for Invoke<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Invoke")
+            .field("done", &self.shared.slot().result.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,40 @@
+//! TODO
+
+use std::ffi::c_void;
+
+use crate::runtime::{get_runtime, RuntimeError};
+
+use super::class::UnityClass;
+
+/// Represents a C# Type, as used by fields, parameters and return values
+#[derive(Debug)]
+pub struct UnityType {
+    /// The inner pointer to the Type
+    pub inner: *mut c_void,
+}
+
+unsafe impl Send for UnityType {}
+unsafe impl Sync for UnityType {}
+
+impl Clone for UnityType {
+    fn clone(&self) -> UnityType {
+        UnityType { ..*self }
+    }
+}
+
+impl UnityType {
+    /// returns the full name of the type, e.g. `System.Int32`
+    pub fn get_name(&self) -> Result<String, RuntimeError> {
+        get_runtime()?.get_type_name(self)
+    }
+
+    /// returns the class of the type
+    pub fn get_class(&self) -> Result<UnityClass, RuntimeError> {
+        get_runtime()?.get_type_class(self)
+    }
+
+    /// returns how many bytes a value of this type takes up, the pointer size for reference types
+    pub fn get_size(&self) -> Result<usize, RuntimeError> {
+        get_runtime()?.get_type_size(self)
+    }
+}
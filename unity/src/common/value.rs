@@ -0,0 +1,63 @@
+//! TODO
+
+use std::ffi::c_void;
+
+use super::{object::UnityObject, string::UnityString};
+
+/// A rust type that can stand in for a managed value
+///
+/// used wherever values are passed to or read from the runtime, such as method arguments and fields.
+/// value types are passed by pointer, reference types by their object pointer.
+/// managed `bool`s are a byte that may hold any value, which isn't a valid rust `bool`, so they're read as `u8`.
+///
+/// # Safety
+///
+/// the type must have the exact layout of the managed value, and any bytes the managed value may hold,
+/// including all zeroes, must be a valid value.
+/// implement this for your own `#[repr(C)]` structs to use them as value types.
+pub unsafe trait ManagedValue {
+    /// whether the value is an object reference, which the gc has to be told about when stored
+    fn is_reference() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// returns the pointer handed to the runtime
+    fn as_arg(&mut self) -> *mut c_void;
+}
+
+macro_rules! impl_value_type {
+    ($($ty:ty),*) => {
+        $(
+            unsafe impl ManagedValue for $ty {
+                fn as_arg(&mut self) -> *mut c_void {
+                    (self as *mut $ty).cast()
+                }
+            }
+        )*
+    };
+}
+
+impl_value_type!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64);
+
+unsafe impl ManagedValue for UnityObject {
+    fn is_reference() -> bool {
+        true
+    }
+
+    fn as_arg(&mut self) -> *mut c_void {
+        self.inner
+    }
+}
+
+unsafe impl ManagedValue for UnityString {
+    fn is_reference() -> bool {
+        true
+    }
+
+    fn as_arg(&mut self) -> *mut c_void {
+        self.inner
+    }
+}
@@ -0,0 +1,302 @@
+//! TODO
+
+use super::{HookError, Relocated};
+
+/// `ldr x17, #8`
+const LDR_X17_8: u32 = 0x5800_0051;
+/// `ldr x17, #12`
+const LDR_X17_12: u32 = 0x5800_0071;
+/// `br x17`
+const BR_X17: u32 = 0xD61F_0220;
+/// `blr x17`
+const BLR_X17: u32 = 0xD63F_0220;
+/// `b #12`
+const B_12: u32 = 0x1400_0003;
+/// `b #20`
+const B_20: u32 = 0x1400_0005;
+/// `nop`
+const NOP: u32 = 0xD503_201F;
+
+/// the patch that jumps from `from` to `to`
+///
+/// a 4 byte `b` if `to` is in range, a 16 byte `ldr x17, #8; br x17` followed by the address otherwise.
+pub fn jump(from: usize, to: usize) -> Vec<u8> {
+    let mut code = Vec::new();
+
+    match branch_offset(from, to, 26) {
+        Some(imm) => push(&mut code, &[0x1400_0000 | imm]),
+        None => absolute_jump(&mut code, to),
+    }
+
+    code
+}
+
+/// moves the instructions covering the first `min_len` bytes of `code`, which lives at `from`, to `to`
+///
+/// pc relative loads, address calculations and branches are rewritten to keep pointing at the same addresses,
+/// using `x17` as scratch register, and a jump back to the rest of the function is appended.
+///
+/// # Errors
+///
+/// * `HookError::UnsupportedInstruction` - if an instruction branches into the moved bytes
+/// * `HookError::TooSmall` - if the function ends before `min_len` bytes
+///
+/// # Examples
+///
+/// ```
+/// use unity_rs::hook::aarch64;
+///
+/// // adr x1, #8; ret
+/// let code = [0x41, 0x00, 0x00, 0x10, 0xC0, 0x03, 0x5F, 0xD6];
+/// let relocated = aarch64::relocate(&code, 0x10000, 0x20000, 4).unwrap();
+///
+/// // ldr x1, #8; b #12; the address adr calculated
+/// assert_eq!(relocated.stolen, 4);
+/// assert_eq!(&relocated.code[..4], &0x5800_0041u32.to_le_bytes());
+/// assert_eq!(&relocated.code[4..8], &0x1400_0003u32.to_le_bytes());
+/// assert_eq!(&relocated.code[8..16], &0x10008u64.to_le_bytes());
+/// assert_eq!(&relocated.code[16..], &aarch64::jump(0x20010, 0x10004)[..]);
+/// ```
+pub fn relocate(code: &[u8], from: usize, to: usize, min_len: usize) -> Result<Relocated, HookError> {
+    let mut out = Vec::new();
+    let mut branches = Vec::new();
+    let mut offset = 0;
+    let mut terminated = false;
+
+    while offset < min_len {
+        let pc = from + offset;
+        let insn = match code.get(offset..offset + 4) {
+            Some(bytes) => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            None => {
+                return Err(HookError::UnsupportedInstruction {
+                    address: pc,
+                    reason: "truncated instruction",
+                })
+            }
+        };
+        offset += 4;
+
+        // the function ended early, we may only overwrite what follows if it's padding
+        if terminated {
+            if !is_padding(insn) {
+                return Err(HookError::TooSmall(from));
+            }
+
+            continue;
+        }
+
+        let at = to + out.len();
+
+        if insn & 0x7C00_0000 == 0x1400_0000 {
+            // b, bl
+            let target = pc.wrapping_add_signed(sign_extend(insn & 0x03FF_FFFF, 26) * 4);
+            branches.push((pc, target));
+
+            if insn & 0x8000_0000 == 0 {
+                out.extend_from_slice(&jump(at, target));
+                terminated = true;
+            } else {
+                match branch_offset(at, target, 26) {
+                    Some(imm) => push(&mut out, &[0x9400_0000 | imm]),
+                    None => {
+                        push(&mut out, &[LDR_X17_12, BLR_X17, B_12]);
+                        out.extend_from_slice(&(target as u64).to_le_bytes());
+                    }
+                }
+            }
+        } else if insn & 0xFF00_0010 == 0x5400_0000 {
+            // b.cond
+            let target = pc.wrapping_add_signed(sign_extend((insn >> 5) & 0x7FFFF, 19) * 4);
+            branches.push((pc, target));
+            emit_conditional(&mut out, at, insn & 0xFF00_001F, 19, target);
+        } else if insn & 0x7E00_0000 == 0x3400_0000 {
+            // cbz, cbnz
+            let target = pc.wrapping_add_signed(sign_extend((insn >> 5) & 0x7FFFF, 19) * 4);
+            branches.push((pc, target));
+            emit_conditional(&mut out, at, insn & 0xFF00_001F, 19, target);
+        } else if insn & 0x7E00_0000 == 0x3600_0000 {
+            // tbz, tbnz
+            let target = pc.wrapping_add_signed(sign_extend((insn >> 5) & 0x3FFF, 14) * 4);
+            branches.push((pc, target));
+            emit_conditional(&mut out, at, insn & 0xFFF8_001F, 14, target);
+        } else if insn & 0x1F00_0000 == 0x1000_0000 {
+            // adr, adrp
+            let rd = insn & 0x1F;
+            let imm = sign_extend((((insn >> 5) & 0x7FFFF) << 2) | ((insn >> 29) & 3), 21);
+
+            let value = match insn & 0x8000_0000 {
+                0 => pc.wrapping_add_signed(imm),
+                _ => (pc & !0xFFF).wrapping_add_signed(imm << 12),
+            };
+
+            push(&mut out, &[0x5800_0040 | rd, B_12]);
+            out.extend_from_slice(&(value as u64).to_le_bytes());
+        } else if insn & 0x3B00_0000 == 0x1800_0000 {
+            // ldr (literal), loaded through x17 instead
+            let rt = insn & 0x1F;
+            let target = pc.wrapping_add_signed(sign_extend((insn >> 5) & 0x7FFFF, 19) * 4);
+
+            let load = match (insn >> 30, (insn >> 26) & 1) {
+                (0, 0) => 0xB940_0000,
+                (1, 0) => 0xF940_0000,
+                (2, 0) => 0xB980_0000,
+                (0, 1) => 0xBD40_0000,
+                (1, 1) => 0xFD40_0000,
+                (2, 1) => 0x3DC0_0000,
+                // prfm, only a hint
+                _ => continue,
+            };
+
+            push(&mut out, &[LDR_X17_12, load | (17 << 5) | rt, B_12]);
+            out.extend_from_slice(&(target as u64).to_le_bytes());
+        } else {
+            push(&mut out, &[insn]);
+
+            // br, ret
+            terminated = insn & 0xFFFF_FC1F == 0xD61F_0000 || insn & 0xFFFF_FC1F == 0xD65F_0000;
+        }
+    }
+
+    // those bytes are about to be overwritten
+    if let Some(&(address, _)) = branches.iter().find(|(_, target)| (from..from + offset).contains(target)) {
+        return Err(HookError::UnsupportedInstruction {
+            address,
+            reason: "branch into the relocated bytes",
+        });
+    }
+
+    if !terminated {
+        let back = jump(to + out.len(), from + offset);
+        out.extend_from_slice(&back);
+    }
+
+    Ok(Relocated {
+        code: out,
+        stolen: offset,
+    })
+}
+
+fn push(out: &mut Vec<u8>, words: &[u32]) {
+    for word in words {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+}
+
+/// `ldr x17, #8; br x17`, followed by the address
+fn absolute_jump(out: &mut Vec<u8>, to: usize) {
+    push(out, &[LDR_X17_8, BR_X17]);
+    out.extend_from_slice(&(to as u64).to_le_bytes());
+}
+
+/// emits a conditional branch to `target`, `insn` being the branch with its offset cleared
+fn emit_conditional(out: &mut Vec<u8>, at: usize, insn: u32, bits: u32, target: usize) {
+    match branch_offset(at, target, bits) {
+        Some(imm) => push(out, &[insn | (imm << 5)]),
+        None => {
+            // taken: skip to the absolute jump, not taken: branch past it
+            push(out, &[insn | (2 << 5), B_20]);
+            absolute_jump(out, target);
+        }
+    }
+}
+
+/// the encoded offset of a branch from `from` to `to`, if it fits into `bits`
+fn branch_offset(from: usize, to: usize, bits: u32) -> Option<u32> {
+    let offset = to.wrapping_sub(from) as isize;
+    if offset % 4 != 0 {
+        return None;
+    }
+
+    let imm = offset / 4;
+    let limit = 1isize << (bits - 1);
+
+    match (-limit..limit).contains(&imm) {
+        true => Some(imm as u32 & ((1 << bits) - 1)),
+        false => None,
+    }
+}
+
+fn sign_extend(value: u32, bits: u32) -> isize {
+    (((value << (32 - bits)) as i32) >> (32 - bits)) as isize
+}
+
+/// nop, udf and brk, which compilers pad functions with
+fn is_padding(insn: u32) -> bool {
+    insn == NOP || insn == 0 || insn & 0xFFE0_001F == 0xD420_0000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: usize = 0x10000;
+    /// far enough from `FROM` that no branch reaches
+    const FAR: usize = 0x7F00_0000_0000;
+
+    fn relocate_insn(insn: u32, to: usize) -> Vec<u8> {
+        relocate(&insn.to_le_bytes(), FROM, to, 4).unwrap().code
+    }
+
+    fn words(words: &[u32]) -> Vec<u8> {
+        let mut out = Vec::new();
+        push(&mut out, words);
+        out
+    }
+
+    /// the inverted-skip form of a conditional branch out of range
+    fn far_conditional(insn: u32, target: usize) -> Vec<u8> {
+        let mut expected = words(&[insn | (2 << 5), B_20]);
+        absolute_jump(&mut expected, target);
+        expected.extend_from_slice(&jump(FAR + expected.len(), FROM + 4));
+        expected
+    }
+
+    #[test]
+    fn bl_out_of_range_calls_through_x17() {
+        // bl #0x100
+        let code = relocate_insn(0x9400_0040, FAR);
+
+        let mut expected = words(&[LDR_X17_12, BLR_X17, B_12]);
+        expected.extend_from_slice(&(FROM as u64 + 0x100).to_le_bytes());
+        expected.extend_from_slice(&jump(FAR + 20, FROM + 4));
+
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn b_cond_is_retargeted() {
+        // b.eq #0x40
+        let near = relocate_insn(0x5400_0200, 0x20000);
+        let imm = ((FROM as isize + 0x40 - 0x20000) / 4) as u32 & 0x7FFFF;
+
+        let mut expected = words(&[0x5400_0000 | (imm << 5)]);
+        expected.extend_from_slice(&jump(0x20004, FROM + 4));
+        assert_eq!(near, expected);
+
+        assert_eq!(relocate_insn(0x5400_0200, FAR), far_conditional(0x5400_0000, FROM + 0x40));
+    }
+
+    #[test]
+    fn cbz_out_of_range_keeps_its_register() {
+        // cbz x3, #0x40
+        assert_eq!(relocate_insn(0xB400_0203, FAR), far_conditional(0xB400_0003, FROM + 0x40));
+    }
+
+    #[test]
+    fn tbz_out_of_range_keeps_its_bit() {
+        // tbz w0, #3, #0x40
+        assert_eq!(relocate_insn(0x3618_0200, FAR), far_conditional(0x3618_0000, FROM + 0x40));
+    }
+
+    #[test]
+    fn ldr_literal_loads_through_x17() {
+        // ldr x1, #0x40
+        let code = relocate_insn(0x5800_0201, 0x20000);
+
+        let mut expected = words(&[LDR_X17_12, 0xF940_0000 | (17 << 5) | 1, B_12]);
+        expected.extend_from_slice(&(FROM as u64 + 0x40).to_le_bytes());
+        expected.extend_from_slice(&jump(0x20014, FROM + 4));
+
+        assert_eq!(code, expected);
+    }
+}
@@ -0,0 +1,321 @@
+//! TODO
+
+use std::{ffi::c_void, io, ptr};
+
+use super::HookError;
+
+/// how far a trampoline may be from its target to still be reached with a rel32
+const NEAR_RANGE: usize = 0x7000_0000;
+/// the distance between addresses tried when looking for memory near a target
+const NEAR_STEP: usize = 0x0100_0000;
+
+/// allocates `size` bytes of read-write memory, as close to `address` as possible
+///
+/// the memory has to be made executable with [`make_executable`] once it's written.
+pub(crate) fn alloc_near(address: usize, size: usize) -> Result<usize, HookError> {
+    for offset in (NEAR_STEP..NEAR_RANGE).step_by(NEAR_STEP) {
+        for hint in [address.checked_sub(offset), address.checked_add(offset)].into_iter().flatten() {
+            if let Some(memory) = alloc_at(hint & !0xFFFF, size) {
+                if memory.abs_diff(address) < NEAR_RANGE {
+                    return Ok(memory);
+                }
+
+                free(memory, size);
+            }
+        }
+    }
+
+    // too far for short jumps, but the jumps fall back to absolute ones
+    alloc_at(0, size).ok_or_else(|| HookError::Alloc(io::Error::last_os_error().to_string()))
+}
+
+#[cfg(target_os = "linux")]
+fn alloc_at(hint: usize, size: usize) -> Option<usize> {
+    let memory = unsafe {
+        libc::mmap(
+            hint as *mut c_void,
+            size,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+
+    match memory == libc::MAP_FAILED {
+        true => None,
+        false => Some(memory as usize),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn alloc_at(hint: usize, size: usize) -> Option<usize> {
+    use winapi::um::{
+        memoryapi::VirtualAlloc,
+        winnt::{MEM_COMMIT, MEM_RESERVE, PAGE_READWRITE},
+    };
+
+    let memory = unsafe { VirtualAlloc(hint as *mut _, size, MEM_COMMIT | MEM_RESERVE, PAGE_READWRITE) };
+
+    match memory.is_null() {
+        true => None,
+        false => Some(memory as usize),
+    }
+}
+
+/// frees memory from [`alloc_near`]
+#[cfg(target_os = "linux")]
+pub(crate) fn free(memory: usize, size: usize) {
+    unsafe { libc::munmap(memory as *mut c_void, size) };
+}
+
+/// frees memory from [`alloc_near`]
+#[cfg(target_os = "windows")]
+pub(crate) fn free(memory: usize, _size: usize) {
+    use winapi::um::{memoryapi::VirtualFree, winnt::MEM_RELEASE};
+
+    unsafe { VirtualFree(memory as *mut _, 0, MEM_RELEASE) };
+}
+
+/// turns memory from [`alloc_near`] read-execute
+#[cfg(target_os = "linux")]
+pub(crate) fn make_executable(memory: usize, size: usize) -> Result<(), HookError> {
+    if unsafe { libc::mprotect(memory as *mut c_void, size, libc::PROT_READ | libc::PROT_EXEC) } != 0 {
+        return Err(HookError::Protect(io::Error::last_os_error().to_string()));
+    }
+
+    flush_instruction_cache(memory, size);
+    Ok(())
+}
+
+/// turns memory from [`alloc_near`] read-execute
+#[cfg(target_os = "windows")]
+pub(crate) fn make_executable(memory: usize, size: usize) -> Result<(), HookError> {
+    use winapi::um::{memoryapi::VirtualProtect, winnt::PAGE_EXECUTE_READ};
+
+    let mut old = 0;
+    if unsafe { VirtualProtect(memory as *mut _, size, PAGE_EXECUTE_READ, &mut old) } == 0 {
+        return Err(HookError::Protect(io::Error::last_os_error().to_string()));
+    }
+
+    flush_instruction_cache(memory, size);
+    Ok(())
+}
+
+/// the size of a memory page
+#[cfg(target_os = "linux")]
+pub(crate) fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+/// the size of a memory page
+#[cfg(target_os = "windows")]
+pub(crate) fn page_size() -> usize {
+    use winapi::um::sysinfoapi::{GetSystemInfo, SYSTEM_INFO};
+
+    let mut info: SYSTEM_INFO = unsafe { std::mem::zeroed() };
+    unsafe { GetSystemInfo(&mut info) };
+    info.dwPageSize as usize
+}
+
+/// overwrites code, making it writable for the duration
+///
+/// the pages get their previous protection back afterwards, since some are meant to stay writable,
+/// like the code chunks mono's jit keeps writing to.
+///
+/// # Safety
+///
+/// no thread may be executing the overwritten bytes
+#[cfg(target_os = "linux")]
+pub(crate) unsafe fn write_code(address: usize, bytes: &[u8]) -> Result<(), HookError> {
+    let page_size = page_size();
+    let start = address & !(page_size - 1);
+    let len = (address + bytes.len() - start).next_multiple_of(page_size);
+
+    let protect = |start: usize, len: usize, flags| match libc::mprotect(start as *mut c_void, len, flags) {
+        0 => Ok(()),
+        _ => Err(HookError::Protect(io::Error::last_os_error().to_string())),
+    };
+
+    let regions = protections(start, len)?;
+
+    protect(start, len, libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC)?;
+    ptr::copy_nonoverlapping(bytes.as_ptr(), address as *mut u8, bytes.len());
+
+    // like on windows, the code is patched even if the protection can't be restored
+    for (start, len, flags) in regions {
+        let _ = protect(start, len, flags);
+    }
+
+    flush_instruction_cache(address, bytes.len());
+    Ok(())
+}
+
+/// the protection of the mappings covering `len` bytes at `start`, as `(start, len, flags)` for `mprotect`
+#[cfg(target_os = "linux")]
+fn protections(start: usize, len: usize) -> Result<Vec<(usize, usize, libc::c_int)>, HookError> {
+    let end = start + len;
+
+    let mut regions = Vec::new();
+    let mut covered = start;
+
+    for (map_start, map_end, flags) in mappings()? {
+        if map_end <= covered || map_start > covered || covered >= end {
+            continue;
+        }
+
+        let region_end = map_end.min(end);
+        regions.push((covered, region_end - covered, flags));
+        covered = region_end;
+    }
+
+    match covered >= end {
+        true => Ok(regions),
+        false => Err(HookError::Protect(format!("{:#x} isn't mapped", covered))),
+    }
+}
+
+/// how many of the `max` bytes at `address` can be read, stopping at the first unreadable page
+#[cfg(target_os = "linux")]
+pub(crate) fn readable_len(address: usize, max: usize) -> Result<usize, HookError> {
+    let end = address + max;
+    let mut covered = address;
+
+    for (map_start, map_end, flags) in mappings()? {
+        if map_end <= covered || map_start > covered || covered >= end {
+            continue;
+        }
+
+        if flags & libc::PROT_READ == 0 {
+            break;
+        }
+
+        covered = map_end.min(end);
+    }
+
+    Ok(covered - address)
+}
+
+/// the mappings of the process, as `(start, end, flags)` in ascending order
+#[cfg(target_os = "linux")]
+fn mappings() -> Result<Vec<(usize, usize, libc::c_int)>, HookError> {
+    let maps = std::fs::read_to_string("/proc/self/maps").map_err(|error| HookError::Protect(error.to_string()))?;
+
+    let mut mappings = Vec::new();
+
+    for line in maps.lines() {
+        let mut columns = line.split_whitespace();
+
+        let (Some(range), Some(permissions)) = (columns.next(), columns.next()) else {
+            continue;
+        };
+
+        let Some((Ok(start), Ok(end))) = range
+            .split_once('-')
+            .map(|(start, end)| (usize::from_str_radix(start, 16), usize::from_str_radix(end, 16)))
+        else {
+            continue;
+        };
+
+        let mut flags = libc::PROT_NONE;
+        for (permission, flag) in permissions.bytes().zip([libc::PROT_READ, libc::PROT_WRITE, libc::PROT_EXEC]) {
+            if permission != b'-' {
+                flags |= flag;
+            }
+        }
+
+        mappings.push((start, end, flags));
+    }
+
+    Ok(mappings)
+}
+
+/// how many of the `max` bytes at `address` can be read, stopping at the first unreadable page
+#[cfg(target_os = "windows")]
+pub(crate) fn readable_len(address: usize, max: usize) -> Result<usize, HookError> {
+    use winapi::um::{
+        memoryapi::VirtualQuery,
+        winnt::{MEMORY_BASIC_INFORMATION, MEM_COMMIT, PAGE_GUARD, PAGE_NOACCESS},
+    };
+
+    let end = address + max;
+    let mut covered = address;
+
+    while covered < end {
+        let mut info: MEMORY_BASIC_INFORMATION = unsafe { std::mem::zeroed() };
+
+        if unsafe { VirtualQuery(covered as *const _, &mut info, std::mem::size_of::<MEMORY_BASIC_INFORMATION>()) } == 0 {
+            return Err(HookError::Protect(io::Error::last_os_error().to_string()));
+        }
+
+        if info.State != MEM_COMMIT || info.Protect & (PAGE_NOACCESS | PAGE_GUARD) != 0 {
+            break;
+        }
+
+        covered = (info.BaseAddress as usize + info.RegionSize).min(end);
+    }
+
+    Ok(covered - address)
+}
+
+/// overwrites code, making it writable for the duration
+///
+/// # Safety
+///
+/// no thread may be executing the overwritten bytes
+#[cfg(target_os = "windows")]
+pub(crate) unsafe fn write_code(address: usize, bytes: &[u8]) -> Result<(), HookError> {
+    use winapi::um::{memoryapi::VirtualProtect, winnt::PAGE_EXECUTE_READWRITE};
+
+    let mut old = 0;
+    if VirtualProtect(address as *mut _, bytes.len(), PAGE_EXECUTE_READWRITE, &mut old) == 0 {
+        return Err(HookError::Protect(io::Error::last_os_error().to_string()));
+    }
+
+    ptr::copy_nonoverlapping(bytes.as_ptr(), address as *mut u8, bytes.len());
+    VirtualProtect(address as *mut _, bytes.len(), old, &mut old);
+
+    flush_instruction_cache(address, bytes.len());
+    Ok(())
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+fn flush_instruction_cache(_address: usize, _len: usize) {
+    // x86 keeps the instruction cache coherent on its own
+}
+
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+fn flush_instruction_cache(address: usize, len: usize) {
+    use std::arch::asm;
+
+    let ctr: u64;
+    unsafe { asm!("mrs {}, ctr_el0", out(reg) ctr) };
+
+    let data_line = 4 << ((ctr >> 16) & 0xF);
+    let instruction_line = 4 << (ctr & 0xF);
+    let end = address + len;
+
+    let mut line = address & !(data_line - 1);
+    while line < end {
+        unsafe { asm!("dc cvau, {}", in(reg) line) };
+        line += data_line;
+    }
+    unsafe { asm!("dsb ish") };
+
+    let mut line = address & !(instruction_line - 1);
+    while line < end {
+        unsafe { asm!("ic ivau, {}", in(reg) line) };
+        line += instruction_line;
+    }
+    unsafe { asm!("dsb ish", "isb") };
+}
+
+#[cfg(all(target_os = "linux", not(any(target_arch = "x86_64", target_arch = "aarch64"))))]
+fn flush_instruction_cache(_address: usize, _len: usize) {}
+
+#[cfg(target_os = "windows")]
+fn flush_instruction_cache(address: usize, len: usize) {
+    use winapi::um::processthreadsapi::{FlushInstructionCache, GetCurrentProcess};
+
+    unsafe { FlushInstructionCache(GetCurrentProcess(), address as *const _, len) };
+}
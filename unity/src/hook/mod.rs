@@ -0,0 +1,438 @@
+//! TODO
+
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::Deref,
+    ptr, slice,
+    sync::{Mutex, PoisonError},
+};
+
+use thiserror::Error;
+
+use crate::{common::method::MethodPointer, libs::NativeMethod, runtime::RuntimeError};
+
+pub mod aarch64;
+mod memory;
+pub mod x86_64;
+
+#[cfg(target_arch = "x86_64")]
+use self::x86_64 as arch;
+
+#[cfg(target_arch = "aarch64")]
+use self::aarch64 as arch;
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod arch {
+    use super::{HookError, Relocated};
+
+    pub fn jump(_from: usize, _to: usize) -> Vec<u8> {
+        Vec::new()
+    }
+
+    pub fn relocate(_code: &[u8], _from: usize, _to: usize, _min_len: usize) -> Result<Relocated, HookError> {
+        Err(HookError::UnsupportedArch)
+    }
+}
+
+/// how many bytes of a function are read to relocate its start
+const MAX_PROLOGUE: usize = 32;
+
+/// the space in front of a trampoline, holding the jump to the detour
+const RELAY_SIZE: usize = 16;
+
+/// the trampolines of all installed hooks by target, also serializing the patching
+static HOOKS: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+/// possible hooking errors
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("Function at {0:#x} is already hooked")]
+    AlreadyHooked(usize),
+    #[error("Unsupported instruction at {address:#x}: {reason}")]
+    UnsupportedInstruction { address: usize, reason: &'static str },
+    #[error("Relocated instruction at {0:#x} can't reach its target")]
+    OutOfRange(usize),
+    #[error("Function at {0:#x} is too small to hook")]
+    TooSmall(usize),
+    #[error("Failed to allocate trampoline: {0}")]
+    Alloc(String),
+    #[error("Failed to change memory protection: {0}")]
+    Protect(String),
+    #[error("Hooking is not supported on this architecture")]
+    UnsupportedArch,
+}
+
+/// the start of a function, moved somewhere else
+#[derive(Debug, Clone)]
+pub struct Relocated {
+    /// the moved instructions, followed by a jump back to the rest of the function
+    pub code: Vec<u8>,
+    /// how many bytes of the function were moved
+    pub stolen: usize,
+}
+
+/// An installed detour
+///
+/// calls to the target end up in the detour, which can call the original through the trampoline.
+/// the hook stays installed until [`Hook::uninstall`] is called.
+#[derive(Debug)]
+pub struct Hook {
+    target: usize,
+    detour: usize,
+    trampoline: usize,
+    original: Vec<u8>,
+}
+
+unsafe impl Send for Hook {}
+unsafe impl Sync for Hook {}
+
+impl Hook {
+    /// redirects every call of `target` to `detour`
+    ///
+    /// the start of `target` is overwritten with a jump, the instructions it replaces are moved into a trampoline.
+    ///
+    /// # Errors
+    ///
+    /// * `HookError::AlreadyHooked` - if `target` is already hooked
+    /// * `HookError::UnsupportedInstruction` - if the start of `target` can't be relocated
+    /// * `HookError::TooSmall` - if `target` is too small to fit the jump
+    ///
+    /// # Safety
+    ///
+    /// `target` and `detour` have to be functions with the same signature and calling convention,
+    /// and no thread may be executing the start of `target` while it's patched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{ffi::c_void, hint::black_box, sync::OnceLock};
+    ///
+    /// use unity_rs::hook::Hook;
+    ///
+    /// #[inline(never)]
+    /// extern "C" fn add(a: i32, b: i32) -> i32 {
+    ///     a + b
+    /// }
+    ///
+    /// static HOOK: OnceLock<Hook> = OnceLock::new();
+    ///
+    /// extern "C" fn detour(a: i32, b: i32) -> i32 {
+    ///     let original = HOOK.get().unwrap().original::<extern "C" fn(i32, i32) -> i32>();
+    ///     original(a, b) * 10
+    /// }
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// let add: extern "C" fn(i32, i32) -> i32 = black_box(add);
+    ///
+    /// let hook = unsafe { Hook::install(add as *mut c_void, detour as *mut c_void)? };
+    /// HOOK.set(hook).unwrap();
+    /// assert_eq!(add(1, 2), 30);
+    ///
+    /// unsafe { HOOK.get().unwrap().uninstall()? };
+    /// assert_eq!(add(1, 2), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn install(target: MethodPointer, detour: MethodPointer) -> Result<Hook, RuntimeError> {
+        if target.is_null() {
+            return Err(RuntimeError::NullPointer("target"));
+        }
+
+        if detour.is_null() {
+            return Err(RuntimeError::NullPointer("detour"));
+        }
+
+        let address = target as usize;
+        let mut hooks = HOOKS.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if hooks.contains_key(&address) {
+            return Err(HookError::AlreadyHooked(address).into());
+        }
+
+        // the page holds the jump to the detour, followed by the trampoline.
+        // allocated near the target, so the patch can be a short jump
+        let page_size = memory::page_size();
+        let page = memory::alloc_near(address, page_size)?;
+        let trampoline = page + RELAY_SIZE;
+
+        let relay = arch::jump(page, detour as usize);
+        let patch = arch::jump(address, page);
+
+        // a short function may end right before an unmapped page, relocating it stops at the end of the slice
+        let code = match memory::readable_len(address, MAX_PROLOGUE) {
+            Ok(len) => slice::from_raw_parts(address as *const u8, len),
+            Err(error) => {
+                memory::free(page, page_size);
+                return Err(error.into());
+            }
+        };
+
+        let relocated = match arch::relocate(code, address, trampoline, patch.len()) {
+            Ok(relocated) => relocated,
+            Err(error) => {
+                memory::free(page, page_size);
+                return Err(error.into());
+            }
+        };
+
+        slice::from_raw_parts_mut(page as *mut u8, relay.len()).copy_from_slice(&relay);
+        slice::from_raw_parts_mut(trampoline as *mut u8, relocated.code.len()).copy_from_slice(&relocated.code);
+        let original = code[..patch.len()].to_vec();
+
+        if let Err(error) = memory::make_executable(page, page_size).and_then(|_| memory::write_code(address, &patch)) {
+            memory::free(page, page_size);
+            return Err(error.into());
+        }
+
+        hooks.insert(address, trampoline);
+
+        Ok(Hook {
+            target: address,
+            detour: detour as usize,
+            trampoline,
+            original,
+        })
+    }
+
+    /// restores the original start of the target
+    ///
+    /// the trampoline is kept alive, since another thread may still be running through it.
+    ///
+    /// # Safety
+    ///
+    /// no thread may be executing the start of the target while it's restored
+    pub unsafe fn uninstall(&self) -> Result<(), RuntimeError> {
+        let mut hooks = HOOKS.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if !self.is_installed_in(&hooks) {
+            return Ok(());
+        }
+
+        memory::write_code(self.target, &self.original)?;
+        hooks.remove(&self.target);
+
+        Ok(())
+    }
+
+    /// whether the hook is still installed
+    pub fn is_installed(&self) -> bool {
+        self.is_installed_in(&HOOKS.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// the target may have been hooked again since this hook was uninstalled
+    fn is_installed_in(&self, hooks: &BTreeMap<usize, usize>) -> bool {
+        hooks.get(&self.target) == Some(&self.trampoline)
+    }
+
+    /// the hooked function
+    pub fn target(&self) -> MethodPointer {
+        self.target as MethodPointer
+    }
+
+    /// the function calls are redirected to
+    pub fn detour(&self) -> MethodPointer {
+        self.detour as MethodPointer
+    }
+
+    /// calling this runs the original function, bypassing the hook
+    pub fn trampoline(&self) -> MethodPointer {
+        self.trampoline as MethodPointer
+    }
+
+    /// the trampoline as a callable function
+    ///
+    /// `T` has to be the `fn` type of the target
+    pub fn original<T>(&self) -> NativeMethod<T> {
+        unsafe { NativeMethod::from_raw(self.trampoline()) }
+    }
+}
+
+/// A hook that's uninstalled when dropped
+///
+/// `T` is the `fn` type of the hooked function, so the original can be called without naming it again.
+/// dropping the guard has the same requirements as [`Hook::uninstall`].
+#[derive(Debug)]
+pub struct HookGuard<T> {
+    hook: Hook,
+    pd: PhantomData<T>,
+}
+
+impl<T> HookGuard<T> {
+    /// takes ownership of an installed hook
+    pub fn new(hook: Hook) -> HookGuard<T> {
+        HookGuard {
+            hook,
+            pd: PhantomData,
+        }
+    }
+
+    /// the trampoline as a callable function, see [`Hook::original`]
+    pub fn original(&self) -> NativeMethod<T> {
+        self.hook.original()
+    }
+
+    /// keeps the hook installed for good
+    pub fn leak(self) -> Hook {
+        let guard = ManuallyDrop::new(self);
+        unsafe { ptr::read(&guard.hook) }
+    }
+}
+
+impl<T> Deref for HookGuard<T> {
+    type Target = Hook;
+
+    fn deref(&self) -> &Hook {
+        &self.hook
+    }
+}
+
+impl<T> Drop for HookGuard<T> {
+    fn drop(&mut self) {
+        // nothing sensible to do with an error while dropping
+        let _ = unsafe { self.hook.uninstall() };
+    }
+}
+
+impl<T> NativeMethod<T> {
+    /// redirects every call of this function to `detour`, see [`Hook::install`]
+    ///
+    /// # Safety
+    ///
+    /// see [`Hook::install`]
+    pub unsafe fn hook(&self, detour: MethodPointer) -> Result<Hook, RuntimeError> {
+        Hook::install(self.inner, detour)
+    }
+}
+
+#[cfg(all(test, target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod tests {
+    use std::{ffi::c_void, hint::black_box, mem};
+
+    use super::*;
+
+    // every test hooks its own function, since tests run in parallel
+
+    #[inline(never)]
+    extern "C" fn add(a: i32, b: i32) -> i32 {
+        a.wrapping_add(b)
+    }
+
+    #[inline(never)]
+    extern "C" fn sub(a: i32, b: i32) -> i32 {
+        a.wrapping_sub(b)
+    }
+
+    #[inline(never)]
+    extern "C" fn mul(a: i32, b: i32) -> i32 {
+        a.wrapping_mul(b)
+    }
+
+    extern "C" fn zero(_a: i32, _b: i32) -> i32 {
+        0
+    }
+
+    type BinaryFn = extern "C" fn(i32, i32) -> i32;
+
+    fn start_of(function: BinaryFn) -> Vec<u8> {
+        unsafe { slice::from_raw_parts(function as *const u8, MAX_PROLOGUE) }.to_vec()
+    }
+
+    #[test]
+    fn hooking_twice_is_rejected() {
+        let add: BinaryFn = black_box(add);
+
+        let hook = unsafe { Hook::install(add as *mut c_void, zero as *mut c_void) }.unwrap();
+        let again = unsafe { Hook::install(add as *mut c_void, zero as *mut c_void) };
+
+        assert!(matches!(again, Err(RuntimeError::Hook(HookError::AlreadyHooked(address))) if address == add as usize));
+
+        unsafe { hook.uninstall() }.unwrap();
+        assert_eq!(add(1, 2), 3);
+    }
+
+    #[test]
+    fn hooks_can_be_reinstalled() {
+        let sub: BinaryFn = black_box(sub);
+
+        let first = unsafe { Hook::install(sub as *mut c_void, zero as *mut c_void) }.unwrap();
+        assert_eq!(sub(5, 2), 0);
+
+        unsafe { first.uninstall() }.unwrap();
+        assert!(!first.is_installed());
+        assert_eq!(sub(5, 2), 3);
+
+        let second = unsafe { Hook::install(sub as *mut c_void, zero as *mut c_void) }.unwrap();
+        assert_eq!(sub(5, 2), 0);
+        assert_eq!(second.original::<BinaryFn>()(5, 2), 3);
+
+        // uninstalling the stale hook mustn't remove the new one
+        unsafe { first.uninstall() }.unwrap();
+        assert!(second.is_installed());
+        assert_eq!(sub(5, 2), 0);
+
+        unsafe { second.uninstall() }.unwrap();
+        assert_eq!(sub(5, 2), 3);
+    }
+
+    #[test]
+    fn dropping_the_guard_restores_the_original_bytes() {
+        let mul: BinaryFn = black_box(mul);
+        let original = start_of(mul);
+
+        let guard = HookGuard::<BinaryFn>::new(unsafe { Hook::install(mul as *mut c_void, zero as *mut c_void) }.unwrap());
+        assert_ne!(start_of(mul), original);
+        assert_eq!(mul(3, 4), 0);
+        assert_eq!(guard.original()(3, 4), 12);
+
+        drop(guard);
+        assert_eq!(start_of(mul), original);
+        assert_eq!(mul(3, 4), 12);
+    }
+
+    extern "C" fn seven() -> i32 {
+        7
+    }
+
+    /// `mov eax, 42`, padding, `ret`
+    #[cfg(target_arch = "x86_64")]
+    const FORTY_TWO: &[u8] = &[0xB8, 0x2A, 0x00, 0x00, 0x00, 0x90, 0x90, 0x90, 0x90, 0x90, 0x90, 0x90, 0x90, 0x90, 0x90, 0xC3];
+
+    /// `mov w0, #42`, padding, `ret`
+    #[cfg(target_arch = "aarch64")]
+    const FORTY_TWO: &[u8] = &[
+        0x40, 0x05, 0x80, 0x52, 0x1F, 0x20, 0x03, 0xD5, 0x1F, 0x20, 0x03, 0xD5, 0x1F, 0x20, 0x03, 0xD5,
+        0x1F, 0x20, 0x03, 0xD5, 0x1F, 0x20, 0x03, 0xD5, 0x1F, 0x20, 0x03, 0xD5, 0xC0, 0x03, 0x5F, 0xD6,
+    ];
+
+    #[test]
+    fn functions_right_before_an_unmapped_page_can_be_hooked() {
+        let page_size = memory::page_size();
+
+        let pages = unsafe {
+            libc::mmap(ptr::null_mut(), 2 * page_size, libc::PROT_READ | libc::PROT_WRITE, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+        };
+        assert_ne!(pages, libc::MAP_FAILED);
+
+        let address = pages as usize + page_size - FORTY_TWO.len();
+
+        unsafe {
+            slice::from_raw_parts_mut(address as *mut u8, FORTY_TWO.len()).copy_from_slice(FORTY_TWO);
+            assert_eq!(libc::mprotect(pages.cast::<u8>().add(page_size).cast(), page_size, libc::PROT_NONE), 0);
+        }
+
+        memory::make_executable(pages as usize, page_size).unwrap();
+        assert_eq!(memory::readable_len(address, MAX_PROLOGUE).unwrap(), FORTY_TWO.len());
+
+        let forty_two: extern "C" fn() -> i32 = unsafe { mem::transmute(address) };
+        assert_eq!(forty_two(), 42);
+
+        let hook = unsafe { Hook::install(address as *mut c_void, seven as *mut c_void) }.unwrap();
+        assert_eq!(forty_two(), 7);
+        assert_eq!(hook.original::<extern "C" fn() -> i32>()(), 42);
+
+        unsafe { hook.uninstall() }.unwrap();
+        assert_eq!(forty_two(), 42);
+    }
+}
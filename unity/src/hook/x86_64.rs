@@ -0,0 +1,476 @@
+//! TODO
+
+use super::{HookError, Relocated};
+
+/// the longest an x86_64 instruction can be
+const MAX_INSTRUCTION: usize = 15;
+
+/// a relative branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Branch {
+    Jmp,
+    Call,
+    /// `jcc`, with the condition code
+    Jcc(u8),
+    /// `loop`, `loope`, `loopne` and `jrcxz`, which only exist with a rel8
+    Loop,
+}
+
+/// how an instruction refers to the address it runs at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    None,
+    /// a `[rip + disp32]` memory operand, with the offset of the displacement
+    RipRelative(usize),
+    /// a relative branch to an absolute target
+    Branch(Branch, usize),
+}
+
+/// a decoded instruction
+#[derive(Debug, Clone, Copy)]
+struct Instruction {
+    len: usize,
+    operand: Operand,
+    /// execution never continues after this instruction
+    terminator: bool,
+    /// an instruction compilers pad functions with
+    padding: bool,
+}
+
+/// the patch that jumps from `from` to `to`
+///
+/// a 5 byte `jmp rel32` if `to` is in range, a 14 byte `jmp [rip]` followed by the address otherwise.
+pub fn jump(from: usize, to: usize) -> Vec<u8> {
+    match rel32(from + 5, to) {
+        Some(rel) => {
+            let mut code = vec![0xE9];
+            code.extend_from_slice(&rel.to_le_bytes());
+            code
+        }
+        None => absolute_jump(to).to_vec(),
+    }
+}
+
+/// moves the instructions covering the first `min_len` bytes of `code`, which lives at `from`, to `to`
+///
+/// rip relative operands and relative branches are rewritten to keep pointing at the same addresses,
+/// and a jump back to the rest of the function is appended.
+///
+/// # Errors
+///
+/// * `HookError::UnsupportedInstruction` - if an instruction can't be decoded, or branches into the moved bytes
+/// * `HookError::OutOfRange` - if a rip relative operand can't reach its target from `to`
+/// * `HookError::TooSmall` - if the function ends before `min_len` bytes
+///
+/// # Examples
+///
+/// ```
+/// use unity_rs::hook::x86_64;
+///
+/// // mov rax, [rip + 0x10]; ret
+/// let code = [0x48, 0x8B, 0x05, 0x10, 0x00, 0x00, 0x00, 0xC3];
+/// let relocated = x86_64::relocate(&code, 0x1000, 0x2000, 5).unwrap();
+///
+/// // still reads 0x1017, now 0x1000 bytes behind
+/// assert_eq!(relocated.stolen, 7);
+/// assert_eq!(&relocated.code[..7], &[0x48, 0x8B, 0x05, 0x10, 0xF0, 0xFF, 0xFF]);
+/// assert_eq!(&relocated.code[7..], &x86_64::jump(0x2007, 0x1007)[..]);
+/// ```
+///
+/// hooking a function starting with a rip relative load, which keeps working from the trampoline
+///
+/// ```
+/// use std::{ffi::c_void, sync::OnceLock};
+///
+/// use unity_rs::hook::Hook;
+///
+/// # #[cfg(target_arch = "x86_64")]
+/// std::arch::global_asm!(
+///     ".globl read_answer",
+///     "read_answer:",
+///     "mov rax, qword ptr [rip + answer]",
+///     "ret",
+///     "answer: .quad 42",
+/// );
+///
+/// extern "C" {
+///     fn read_answer() -> u64;
+/// }
+///
+/// static HOOK: OnceLock<Hook> = OnceLock::new();
+///
+/// extern "C" fn detour() -> u64 {
+///     let original = HOOK.get().unwrap().original::<extern "C" fn() -> u64>();
+///     original() + 1
+/// }
+///
+/// # #[cfg(target_arch = "x86_64")]
+/// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+/// let hook = unsafe { Hook::install(read_answer as *mut c_void, detour as *mut c_void)? };
+/// HOOK.set(hook).unwrap();
+///
+/// assert_eq!(unsafe { read_answer() }, 43);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(target_arch = "x86_64"))]
+/// # fn main() {}
+/// ```
+pub fn relocate(code: &[u8], from: usize, to: usize, min_len: usize) -> Result<Relocated, HookError> {
+    let mut out = Vec::new();
+    let mut branches = Vec::new();
+    let mut offset = 0;
+    let mut terminated = false;
+
+    while offset < min_len {
+        let address = from + offset;
+        let instruction = decode(code.get(offset..).unwrap_or_default(), address)?;
+        let bytes = &code[offset..offset + instruction.len];
+        offset += instruction.len;
+
+        // the function ended early, we may only overwrite what follows if it's padding
+        if terminated {
+            if !instruction.padding {
+                return Err(HookError::TooSmall(from));
+            }
+
+            continue;
+        }
+
+        let at = to + out.len();
+
+        match instruction.operand {
+            Operand::None => out.extend_from_slice(bytes),
+            Operand::RipRelative(disp) => {
+                let old = i32::from_le_bytes([bytes[disp], bytes[disp + 1], bytes[disp + 2], bytes[disp + 3]]);
+                let target = (address + instruction.len).wrapping_add_signed(old as isize);
+                let new = rel32(at + instruction.len, target).ok_or(HookError::OutOfRange(address))?;
+
+                let start = out.len();
+                out.extend_from_slice(bytes);
+                out[start + disp..start + disp + 4].copy_from_slice(&new.to_le_bytes());
+            }
+            Operand::Branch(branch, target) => {
+                branches.push((address, target));
+                emit_branch(&mut out, at, branch, bytes, target);
+            }
+        }
+
+        terminated = instruction.terminator;
+    }
+
+    // those bytes are about to be overwritten
+    if let Some(&(address, _)) = branches.iter().find(|(_, target)| (from..from + offset).contains(target)) {
+        return Err(HookError::UnsupportedInstruction {
+            address,
+            reason: "branch into the relocated bytes",
+        });
+    }
+
+    if !terminated {
+        let back = jump(to + out.len(), from + offset);
+        out.extend_from_slice(&back);
+    }
+
+    Ok(Relocated {
+        code: out,
+        stolen: offset,
+    })
+}
+
+/// `jmp [rip]`, followed by the address
+fn absolute_jump(to: usize) -> [u8; 14] {
+    let mut code = [0xFF, 0x25, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    code[6..].copy_from_slice(&(to as u64).to_le_bytes());
+    code
+}
+
+/// the rel32 reaching `target` from the instruction ending at `next`
+fn rel32(next: usize, target: usize) -> Option<i32> {
+    i32::try_from(target.wrapping_sub(next) as isize).ok()
+}
+
+/// emits a branch to `target`, using the short form where it still reaches
+fn emit_branch(out: &mut Vec<u8>, at: usize, branch: Branch, bytes: &[u8], target: usize) {
+    match branch {
+        Branch::Jmp => out.extend_from_slice(&jump(at, target)),
+        Branch::Call => match rel32(at + 5, target) {
+            Some(rel) => {
+                out.push(0xE8);
+                out.extend_from_slice(&rel.to_le_bytes());
+            }
+            None => {
+                // call [rip + 2]; jmp +8; the address
+                out.extend_from_slice(&[0xFF, 0x15, 0x02, 0x00, 0x00, 0x00, 0xEB, 0x08]);
+                out.extend_from_slice(&(target as u64).to_le_bytes());
+            }
+        },
+        Branch::Jcc(condition) => match rel32(at + 6, target) {
+            Some(rel) => {
+                out.extend_from_slice(&[0x0F, 0x80 | condition]);
+                out.extend_from_slice(&rel.to_le_bytes());
+            }
+            None => {
+                // the inverted condition skips the absolute jump
+                out.extend_from_slice(&[0x70 | (condition ^ 1), 14]);
+                out.extend_from_slice(&absolute_jump(target));
+            }
+        },
+        Branch::Loop => {
+            // taken: skip the `jmp +14` onto the absolute jump, not taken: skip the absolute jump
+            out.extend_from_slice(&bytes[..bytes.len() - 1]);
+            out.extend_from_slice(&[0x02, 0xEB, 14]);
+            out.extend_from_slice(&absolute_jump(target));
+        }
+    }
+}
+
+/// decodes the length and pc relative operand of the instruction at the start of `code`
+fn decode(code: &[u8], address: usize) -> Result<Instruction, HookError> {
+    let unsupported = |reason| HookError::UnsupportedInstruction { address, reason };
+    let byte = |index: usize| code.get(index).copied().ok_or(unsupported("truncated instruction"));
+
+    let mut i = 0;
+    let mut operand_size = false;
+
+    loop {
+        match byte(i)? {
+            0x66 => operand_size = true,
+            0x67 | 0xF0 | 0xF2 | 0xF3 | 0x26 | 0x2E | 0x36 | 0x3E | 0x64 | 0x65 => {}
+            _ => break,
+        }
+        i += 1;
+    }
+
+    let mut rex_w = false;
+    if byte(i)? & 0xF0 == 0x40 {
+        rex_w = byte(i)? & 0x08 != 0;
+        i += 1;
+    }
+
+    // the opcode map, 0 for one byte opcodes, 1 for 0F, 2 for 0F38 and 3 for 0F3A
+    let (map, opcode) = match byte(i)? {
+        0xC5 => {
+            i += 2;
+            (1, byte(i)?)
+        }
+        0xC4 => {
+            let map = byte(i + 1)? & 0x1F;
+            i += 3;
+            (map, byte(i)?)
+        }
+        0x62 => {
+            let map = byte(i + 1)? & 0x07;
+            i += 4;
+            (map, byte(i)?)
+        }
+        0x0F => match byte(i + 1)? {
+            0x38 => {
+                i += 2;
+                (2, byte(i)?)
+            }
+            0x3A => {
+                i += 2;
+                (3, byte(i)?)
+            }
+            opcode => {
+                i += 1;
+                (1, opcode)
+            }
+        },
+        opcode => (0, opcode),
+    };
+    i += 1;
+
+    let imm_z = if operand_size { 2 } else { 4 };
+
+    let mut modrm = true;
+    let mut imm = 0;
+    let mut branch = None;
+    let mut terminator = false;
+    let mut padding = false;
+
+    match (map, opcode) {
+        (0, 0x00..=0x3F) => match opcode & 7 {
+            0..=3 => {}
+            4 => (modrm, imm) = (false, 1),
+            5 => (modrm, imm) = (false, imm_z),
+            _ => return Err(unsupported("invalid in 64-bit mode")),
+        },
+        (0, 0x50..=0x5F | 0x6C..=0x6F | 0x91..=0x99 | 0x9B..=0x9F | 0xA4..=0xA7 | 0xAA..=0xAF) => modrm = false,
+        (0, 0x63 | 0x84..=0x8F | 0xD0..=0xD3 | 0xD8..=0xDF | 0xF6 | 0xF7 | 0xFE | 0xFF) => {}
+        (0, 0x68 | 0xA9) => (modrm, imm) = (false, imm_z),
+        (0, 0x69 | 0x81 | 0xC7) => imm = imm_z,
+        (0, 0x6A | 0xA8 | 0xB0..=0xB7 | 0xCD | 0xE4..=0xE7) => (modrm, imm) = (false, 1),
+        (0, 0x6B | 0x80 | 0x83 | 0xC0 | 0xC1 | 0xC6) => imm = 1,
+        (0, 0x70..=0x7F) => (modrm, branch) = (false, Some((Branch::Jcc(opcode & 0x0F), 1))),
+        (0, 0x90 | 0xCC) => (modrm, padding) = (false, true),
+        (0, 0xA0..=0xA3) => (modrm, imm) = (false, 8),
+        (0, 0xB8..=0xBF) => (modrm, imm) = (false, if rex_w { 8 } else { imm_z }),
+        (0, 0xC2 | 0xCA) => (modrm, imm, terminator) = (false, 2, true),
+        (0, 0xC3 | 0xCB | 0xCF) => (modrm, terminator) = (false, true),
+        (0, 0xC8) => (modrm, imm) = (false, 3),
+        (0, 0xC9 | 0xD7 | 0xEC..=0xEF | 0xF1 | 0xF4 | 0xF5 | 0xF8..=0xFD) => modrm = false,
+        (0, 0xE0..=0xE3) => (modrm, branch) = (false, Some((Branch::Loop, 1))),
+        (0, 0xE8) => (modrm, branch) = (false, Some((Branch::Call, 4))),
+        (0, 0xE9) => (modrm, branch, terminator) = (false, Some((Branch::Jmp, 4)), true),
+        (0, 0xEB) => (modrm, branch, terminator) = (false, Some((Branch::Jmp, 1)), true),
+        (0, _) => return Err(unsupported("invalid in 64-bit mode")),
+
+        (1, 0x0F) => return Err(unsupported("3DNow! is not supported")),
+        (1, 0x05..=0x09 | 0x0B | 0x0E | 0x30..=0x37 | 0x77 | 0xA0..=0xA2 | 0xA8..=0xAA | 0xC8..=0xCF) => modrm = false,
+        (1, 0x1F) => padding = true,
+        (1, 0x70..=0x73 | 0xA4 | 0xAC | 0xBA | 0xC2 | 0xC4..=0xC6) => imm = 1,
+        (1, 0x80..=0x8F) => (modrm, branch) = (false, Some((Branch::Jcc(opcode & 0x0F), 4))),
+        (1, _) | (2, _) => {}
+        (3, _) => imm = 1,
+        _ => return Err(unsupported("unknown opcode map")),
+    }
+
+    let mut operand = Operand::None;
+
+    if modrm {
+        let modrm = byte(i)?;
+        i += 1;
+
+        let mode = modrm >> 6;
+        let reg = (modrm >> 3) & 7;
+        let rm = modrm & 7;
+
+        if mode != 3 {
+            if rm == 4 {
+                let sib = byte(i)?;
+                i += 1;
+
+                if mode == 0 && sib & 7 == 5 {
+                    i += 4;
+                }
+            } else if mode == 0 && rm == 5 {
+                operand = Operand::RipRelative(i);
+                i += 4;
+            }
+
+            match mode {
+                1 => i += 1,
+                2 => i += 4,
+                _ => {}
+            }
+        }
+
+        match (map, opcode, reg) {
+            // test has an immediate, the rest of the group doesn't
+            (0, 0xF6, 0 | 1) => imm = 1,
+            (0, 0xF7, 0 | 1) => imm = imm_z,
+            // indirect jmp
+            (0, 0xFF, 4 | 5) => terminator = true,
+            _ => {}
+        }
+    }
+
+    if let Some((kind, size)) = branch {
+        let rel = match size {
+            1 => byte(i)? as i8 as isize,
+            _ => i32::from_le_bytes([byte(i)?, byte(i + 1)?, byte(i + 2)?, byte(i + 3)?]) as isize,
+        };
+        i += size;
+
+        operand = Operand::Branch(kind, (address + i).wrapping_add_signed(rel));
+    }
+
+    i += imm;
+
+    if i > MAX_INSTRUCTION {
+        return Err(unsupported("instruction too long"));
+    }
+
+    // make sure the whole instruction is there
+    byte(i - 1)?;
+
+    Ok(Instruction {
+        len: i,
+        operand,
+        terminator,
+        padding,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// far enough from the test code that no rel32 reaches
+    const FAR: usize = 0x7F00_0000_0000;
+
+    fn relocate_branch(code: &[u8], to: usize) -> Relocated {
+        relocate(code, 0x1000, to, code.len()).unwrap()
+    }
+
+    #[test]
+    fn jcc_rel8_is_widened() {
+        // je +0x10
+        let relocated = relocate_branch(&[0x74, 0x10], 0x2000);
+
+        let mut expected = vec![0x0F, 0x84];
+        expected.extend_from_slice(&(0x1012i32 - 0x2006).to_le_bytes());
+        expected.extend_from_slice(&jump(0x2006, 0x1002));
+
+        assert_eq!(relocated.stolen, 2);
+        assert_eq!(relocated.code, expected);
+    }
+
+    #[test]
+    fn jcc_rel32_out_of_range_is_inverted() {
+        // jne +0x100
+        let relocated = relocate_branch(&[0x0F, 0x85, 0x00, 0x01, 0x00, 0x00], FAR);
+
+        let mut expected = vec![0x74, 14];
+        expected.extend_from_slice(&absolute_jump(0x1106));
+        expected.extend_from_slice(&jump(FAR + 16, 0x1006));
+
+        assert_eq!(relocated.code, expected);
+    }
+
+    #[test]
+    fn call_out_of_range_goes_through_memory() {
+        // call +0x10
+        let relocated = relocate_branch(&[0xE8, 0x10, 0x00, 0x00, 0x00], FAR);
+
+        let mut expected = vec![0xFF, 0x15, 0x02, 0x00, 0x00, 0x00, 0xEB, 0x08];
+        expected.extend_from_slice(&0x1015u64.to_le_bytes());
+        expected.extend_from_slice(&jump(FAR + 16, 0x1005));
+
+        assert_eq!(relocated.code, expected);
+    }
+
+    #[test]
+    fn loop_and_jrcxz_branch_to_an_absolute_jump() {
+        for opcode in [0xE2, 0xE3] {
+            // loop +0x10, jrcxz +0x10
+            let relocated = relocate_branch(&[opcode, 0x10], 0x2000);
+
+            let mut expected = vec![opcode, 0x02, 0xEB, 14];
+            expected.extend_from_slice(&absolute_jump(0x1012));
+            expected.extend_from_slice(&jump(0x2000 + expected.len(), 0x1002));
+
+            assert_eq!(relocated.code, expected);
+        }
+    }
+
+    #[test]
+    fn jmp_rel8_ends_the_relocation() {
+        // jmp +0x10
+        let near = relocate_branch(&[0xEB, 0x10], 0x2000);
+        assert_eq!(near.code, jump(0x2000, 0x1012));
+        assert_eq!(near.code.len(), 5);
+
+        let far = relocate_branch(&[0xEB, 0x10], FAR);
+        assert_eq!(far.code, absolute_jump(0x1012));
+    }
+
+    #[test]
+    fn branch_into_the_relocated_bytes_is_rejected() {
+        // jne -2, onto itself
+        let result = relocate(&[0x75, 0xFE, 0x90, 0x90, 0x90], 0x1000, 0x2000, 5);
+
+        assert!(matches!(result, Err(HookError::UnsupportedInstruction { address: 0x1000, .. })));
+    }
+}
@@ -0,0 +1,112 @@
+//! TODO
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    mem, ptr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    common::{callback, method::MethodPointer},
+    hook::Hook,
+    libs::NativeMethod,
+    mono::{types::AssemblyName, AssemblyResolver},
+    runtime::RuntimeError,
+};
+
+use super::types::{Il2CppAssembly, Il2CppDomain};
+
+/// the `fn` type of `il2cpp_domain_assembly_open`
+type AssemblyOpen = extern "C" fn(*mut Il2CppDomain, *const c_char) -> *mut Il2CppAssembly;
+
+/// when a resolver runs, relative to the original `il2cpp_domain_assembly_open`
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Stage {
+    /// before, overriding what the runtime would open
+    Preload,
+    /// after, if the runtime couldn't find the assembly
+    Search,
+}
+
+/// the emulated assembly hooks
+struct Resolvers {
+    /// the hook on `il2cpp_domain_assembly_open`, installed along with the first resolver
+    hook: Option<Hook>,
+    preload: Vec<Arc<AssemblyResolver>>,
+    search: Vec<Arc<AssemblyResolver>>,
+}
+
+static RESOLVERS: Mutex<Resolvers> = Mutex::new(Resolvers {
+    hook: None,
+    preload: Vec::new(),
+    search: Vec::new(),
+});
+
+fn resolvers() -> MutexGuard<'static, Resolvers> {
+    RESOLVERS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// adds a resolver, hooking `open` if that hasn't happened yet
+///
+/// # Arguments
+///
+/// * `open` - the `il2cpp_domain_assembly_open` export
+/// * `stage` - whether the resolver runs before or after the runtime looks for the assembly
+/// * `resolver` - the resolver to add
+pub(super) fn add_resolver<T>(open: &NativeMethod<T>, stage: Stage, resolver: AssemblyResolver) -> Result<(), RuntimeError> {
+    let mut resolvers = resolvers();
+
+    if resolvers.hook.is_none() {
+        resolvers.hook = Some(unsafe { open.hook(assembly_open_detour as MethodPointer) }?);
+    }
+
+    match stage {
+        Stage::Preload => resolvers.preload.push(Arc::new(resolver)),
+        Stage::Search => resolvers.search.push(Arc::new(resolver)),
+    }
+
+    Ok(())
+}
+
+/// stands in for `il2cpp_domain_assembly_open`, asking the resolvers around the original
+extern "C" fn assembly_open_detour(domain: *mut Il2CppDomain, name: *const c_char) -> *mut Il2CppAssembly {
+    // copied out, so resolvers are free to open assemblies themselves
+    let (original, preload, search) = {
+        let resolvers = resolvers();
+
+        match &resolvers.hook {
+            Some(hook) => (hook.original::<AssemblyOpen>(), resolvers.preload.clone(), resolvers.search.clone()),
+            None => return ptr::null_mut(),
+        }
+    };
+
+    if name.is_null() {
+        return original(domain, name);
+    }
+
+    // mono hands hooks the simple name, while il2cpp is usually asked for the file name
+    let file_name = unsafe { CStr::from_ptr(name) }.to_bytes();
+    let simple_name = CString::new(file_name.strip_suffix(b".dll").unwrap_or(file_name)).unwrap_or_default();
+
+    let mut assembly_name: AssemblyName = unsafe { mem::zeroed() };
+    assembly_name.name = simple_name.as_ptr().cast_mut();
+
+    let resolve = |hook: &str, resolvers: &[Arc<AssemblyResolver>]| {
+        resolvers
+            .iter()
+            .find_map(|resolver| callback::catch(hook, || resolver(&assembly_name)).flatten())
+            .map(|assembly| assembly.inner.cast())
+    };
+
+    if let Some(assembly) = resolve("assembly preload hook", &preload) {
+        return assembly;
+    }
+
+    let assembly = original(domain, name);
+
+    if !assembly.is_null() {
+        return assembly;
+    }
+
+    resolve("assembly search hook", &search).unwrap_or(ptr::null_mut())
+}
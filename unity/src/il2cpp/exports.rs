@@ -1,40 +1,252 @@
 //! TODO
 
-use std::ffi::c_char;
+#![allow(clippy::type_complexity)]
+
+use std::ffi::{c_char, c_int};
 
 use libc::c_void;
 
-use crate::libs::{LibError, NativeLibrary, NativeMethod};
-
-use super::types::{Il2CppDomain, Il2CppThread, Il2CppObject, Il2CppMethod};
-
-/// Various methods exported by il2cpp
-///
-/// These are direct function pointers, and as such can be hooked
-#[derive(Debug, Clone)]
-pub struct Il2CppExports {
-    /// initializes an il2cpp domain, this is called by unity itself
-    pub il2cpp_init: Option<NativeMethod<fn(*const c_char) -> *mut Il2CppDomain>>,
-    /// returns the current thread
-    pub il2cpp_thread_current: Option<NativeMethod<fn() -> *mut Il2CppThread>>,
-    pub il2cpp_runtime_invoke: Option<NativeMethod<fn(*mut Il2CppMethod, *mut Il2CppObject, *mut *mut c_void, *mut *mut Il2CppObject) -> *mut Il2CppObject>>,
-    pub il2cpp_method_get_name: Option<NativeMethod<fn(*mut Il2CppMethod) -> *const c_char>>,
-    pub il2cpp_thread_attach: Option<NativeMethod<fn(*mut Il2CppDomain) -> *mut Il2CppThread>>,
-    pub il2cpp_domain_get: Option<NativeMethod<fn() -> *mut Il2CppDomain>>,
-    pub il2cpp_add_internal_call: Option<NativeMethod<fn(*const c_char, *mut c_void)>>,
-}
+use crate::native_exports;
+
+use super::types::{
+    Il2CppArray, Il2CppAssembly, Il2CppClass, Il2CppCustomAttrInfo, Il2CppDomain, Il2CppEvent,
+    Il2CppException, Il2CppField, Il2CppImage, Il2CppMethod, Il2CppObject, Il2CppProperty,
+    Il2CppReflectionMethod, Il2CppReflectionType, Il2CppString, Il2CppThread, Il2CppType,
+};
+
+native_exports! {
+    /// Various methods exported by il2cpp, covering `il2cpp-api-functions.h`
+    ///
+    /// These are direct function pointers, and as such can be hooked.
+    /// `optional` exports don't exist on every unity version, and are `None` when missing.
+    #[derive(Debug, Clone)]
+    pub struct Il2CppExports {
+        // initialization
+        /// initializes the il2cpp runtime, this is called by unity itself
+        required il2cpp_init: fn(*const c_char) -> c_int,
+        optional il2cpp_init_utf16: fn(*const u16) -> c_int,
+        optional il2cpp_shutdown: fn(),
+        optional il2cpp_set_config_dir: fn(*const c_char),
+        optional il2cpp_set_data_dir: fn(*const c_char),
+        optional il2cpp_set_temp_dir: fn(*const c_char),
+        optional il2cpp_set_commandline_arguments: fn(c_int, *const *const c_char, *const c_char),
+        optional il2cpp_set_commandline_arguments_utf16: fn(c_int, *const *const u16, *const c_char),
+        optional il2cpp_set_config_utf16: fn(*const u16),
+        optional il2cpp_set_config: fn(*const c_char),
+        optional il2cpp_set_memory_callbacks: fn(*mut c_void),
+        required il2cpp_get_corlib: fn() -> *mut Il2CppImage,
+        required il2cpp_add_internal_call: fn(*const c_char, *mut c_void),
+        optional il2cpp_resolve_icall: fn(*const c_char) -> *mut c_void,
+        optional il2cpp_alloc: fn(usize) -> *mut c_void,
+        required il2cpp_free: fn(*mut c_void),
+
+        // array
+        optional il2cpp_array_class_get: fn(*mut Il2CppClass, u32) -> *mut Il2CppClass,
+        required il2cpp_array_length: fn(*mut Il2CppArray) -> u32,
+        optional il2cpp_array_get_byte_length: fn(*mut Il2CppArray) -> u32,
+        required il2cpp_array_new: fn(*mut Il2CppClass, usize) -> *mut Il2CppArray,
+        optional il2cpp_array_new_specific: fn(*mut Il2CppClass, usize) -> *mut Il2CppArray,
+        optional il2cpp_array_new_full: fn(*mut Il2CppClass, *mut usize, *mut usize) -> *mut Il2CppArray,
+        optional il2cpp_bounded_array_class_get: fn(*mut Il2CppClass, u32, bool) -> *mut Il2CppClass,
+        optional il2cpp_array_element_size: fn(*mut Il2CppClass) -> c_int,
+
+        // assembly
+        required il2cpp_assembly_get_image: fn(*mut Il2CppAssembly) -> *mut Il2CppImage,
+
+        // class
+        optional il2cpp_class_for_each: fn(extern "C" fn(*mut Il2CppClass, *mut c_void), *mut c_void),
+        optional il2cpp_class_enum_basetype: fn(*mut Il2CppClass) -> *mut Il2CppType,
+        required il2cpp_class_is_generic: fn(*mut Il2CppClass) -> bool,
+        optional il2cpp_class_is_inflated: fn(*mut Il2CppClass) -> bool,
+        required il2cpp_class_is_assignable_from: fn(*mut Il2CppClass, *mut Il2CppClass) -> bool,
+        optional il2cpp_class_is_subclass_of: fn(*mut Il2CppClass, *mut Il2CppClass, bool) -> bool,
+        optional il2cpp_class_has_parent: fn(*mut Il2CppClass, *mut Il2CppClass) -> bool,
+        optional il2cpp_class_from_il2cpp_type: fn(*mut Il2CppType) -> *mut Il2CppClass,
+        required il2cpp_class_from_name: fn(*mut Il2CppImage, *const c_char, *const c_char) -> *mut Il2CppClass,
+        optional il2cpp_class_from_system_type: fn(*mut Il2CppReflectionType) -> *mut Il2CppClass,
+        optional il2cpp_class_get_element_class: fn(*mut Il2CppClass) -> *mut Il2CppClass,
+        optional il2cpp_class_get_events: fn(*mut Il2CppClass, *mut *mut c_void) -> *mut Il2CppEvent,
+        required il2cpp_class_get_fields: fn(*mut Il2CppClass, *mut *mut c_void) -> *mut Il2CppField,
+        required il2cpp_class_get_nested_types: fn(*mut Il2CppClass, *mut *mut c_void) -> *mut Il2CppClass,
+        required il2cpp_class_get_interfaces: fn(*mut Il2CppClass, *mut *mut c_void) -> *mut Il2CppClass,
+        required il2cpp_class_get_properties: fn(*mut Il2CppClass, *mut *mut c_void) -> *mut Il2CppProperty,
+        required il2cpp_class_get_property_from_name: fn(*mut Il2CppClass, *const c_char) -> *mut Il2CppProperty,
+        required il2cpp_class_get_field_from_name: fn(*mut Il2CppClass, *const c_char) -> *mut Il2CppField,
+        required il2cpp_class_get_methods: fn(*mut Il2CppClass, *mut *mut c_void) -> *mut Il2CppMethod,
+        required il2cpp_class_get_method_from_name: fn(*mut Il2CppClass, *const c_char, c_int) -> *mut Il2CppMethod,
+        required il2cpp_class_get_name: fn(*mut Il2CppClass) -> *const c_char,
+        required il2cpp_class_get_namespace: fn(*mut Il2CppClass) -> *const c_char,
+        required il2cpp_class_get_parent: fn(*mut Il2CppClass) -> *mut Il2CppClass,
+        optional il2cpp_class_get_declaring_type: fn(*mut Il2CppClass) -> *mut Il2CppClass,
+        optional il2cpp_class_instance_size: fn(*mut Il2CppClass) -> i32,
+        optional il2cpp_class_num_fields: fn(*mut Il2CppClass) -> usize,
+        required il2cpp_class_is_valuetype: fn(*mut Il2CppClass) -> bool,
+        required il2cpp_class_value_size: fn(*mut Il2CppClass, *mut u32) -> i32,
+        optional il2cpp_class_is_blittable: fn(*mut Il2CppClass) -> bool,
+        required il2cpp_class_get_flags: fn(*mut Il2CppClass) -> c_int,
+        optional il2cpp_class_is_abstract: fn(*mut Il2CppClass) -> bool,
+        optional il2cpp_class_is_interface: fn(*mut Il2CppClass) -> bool,
+        required il2cpp_class_array_element_size: fn(*mut Il2CppClass) -> c_int,
+        required il2cpp_class_from_type: fn(*mut Il2CppType) -> *mut Il2CppClass,
+        optional il2cpp_class_get_type: fn(*mut Il2CppClass) -> *mut Il2CppType,
+        optional il2cpp_class_get_type_token: fn(*mut Il2CppClass) -> u32,
+        optional il2cpp_class_has_attribute: fn(*mut Il2CppClass, *mut Il2CppClass) -> bool,
+        optional il2cpp_class_has_references: fn(*mut Il2CppClass) -> bool,
+        optional il2cpp_class_is_enum: fn(*mut Il2CppClass) -> bool,
+        optional il2cpp_class_get_image: fn(*mut Il2CppClass) -> *mut Il2CppImage,
+        optional il2cpp_class_get_assemblyname: fn(*mut Il2CppClass) -> *const c_char,
+        optional il2cpp_class_get_rank: fn(*mut Il2CppClass) -> c_int,
+        optional il2cpp_class_get_data_size: fn(*mut Il2CppClass) -> u32,
+        optional il2cpp_class_get_static_field_data: fn(*mut Il2CppClass) -> *mut c_void,
+        optional il2cpp_class_get_bitmap_size: fn(*mut Il2CppClass) -> usize,
+        optional il2cpp_class_get_bitmap: fn(*mut Il2CppClass, *mut usize),
+        optional il2cpp_class_set_userdata: fn(*mut Il2CppClass, *mut c_void),
+        optional il2cpp_class_get_userdata_offset: fn() -> c_int,
+
+        // domain
+        required il2cpp_domain_get: fn() -> *mut Il2CppDomain,
+        required il2cpp_domain_assembly_open: fn(*mut Il2CppDomain, *const c_char) -> *mut Il2CppAssembly,
+        required il2cpp_domain_get_assemblies: fn(*mut Il2CppDomain, *mut usize) -> *mut *mut Il2CppAssembly,
+
+        // exception
+        required il2cpp_raise_exception: fn(*mut Il2CppException),
+        required il2cpp_exception_from_name_msg: fn(*mut Il2CppImage, *const c_char, *const c_char, *const c_char) -> *mut Il2CppException,
+        optional il2cpp_get_exception_argument_null: fn(*const c_char) -> *mut Il2CppException,
+        optional il2cpp_format_exception: fn(*mut Il2CppException, *mut c_char, c_int),
+        optional il2cpp_format_stack_trace: fn(*mut Il2CppException, *mut c_char, c_int),
+        optional il2cpp_unhandled_exception: fn(*mut Il2CppException),
+
+        // field
+        required il2cpp_field_get_flags: fn(*mut Il2CppField) -> c_int,
+        required il2cpp_field_get_name: fn(*mut Il2CppField) -> *const c_char,
+        optional il2cpp_field_get_parent: fn(*mut Il2CppField) -> *mut Il2CppClass,
+        required il2cpp_field_get_offset: fn(*mut Il2CppField) -> usize,
+        required il2cpp_field_get_type: fn(*mut Il2CppField) -> *mut Il2CppType,
+        required il2cpp_field_get_value: fn(*mut Il2CppObject, *mut Il2CppField, *mut c_void),
+        optional il2cpp_field_get_value_object: fn(*mut Il2CppField, *mut Il2CppObject) -> *mut Il2CppObject,
+        optional il2cpp_field_has_attribute: fn(*mut Il2CppField, *mut Il2CppClass) -> bool,
+        required il2cpp_field_set_value: fn(*mut Il2CppObject, *mut Il2CppField, *mut c_void),
+        required il2cpp_field_static_get_value: fn(*mut Il2CppField, *mut c_void),
+        required il2cpp_field_static_set_value: fn(*mut Il2CppField, *mut c_void),
+        optional il2cpp_field_set_value_object: fn(*mut Il2CppObject, *mut Il2CppField, *mut Il2CppObject),
+        optional il2cpp_field_is_literal: fn(*mut Il2CppField) -> bool,
+
+        // gc
+        optional il2cpp_gc_collect: fn(c_int),
+        optional il2cpp_gc_collect_a_little: fn() -> i32,
+        optional il2cpp_gc_disable: fn(),
+        optional il2cpp_gc_enable: fn(),
+        optional il2cpp_gc_is_disabled: fn() -> bool,
+        optional il2cpp_gc_get_used_size: fn() -> i64,
+        optional il2cpp_gc_get_heap_size: fn() -> i64,
+        optional il2cpp_gc_wbarrier_set_field: fn(*mut Il2CppObject, *mut *mut c_void, *mut c_void),
+
+        // gchandle
+        /// gchandles are `uint32_t` on older versions and pointer sized on newer ones, `usize` covers both as argument.
+        /// returned ones have to be truncated to `u32`, since older versions leave the upper half undefined
+        required il2cpp_gchandle_new: fn(*mut Il2CppObject, bool) -> usize,
+        required il2cpp_gchandle_new_weakref: fn(*mut Il2CppObject, bool) -> usize,
+        required il2cpp_gchandle_get_target: fn(usize) -> *mut Il2CppObject,
+        required il2cpp_gchandle_free: fn(usize),
+
+        // method
+        required il2cpp_method_get_return_type: fn(*mut Il2CppMethod) -> *mut Il2CppType,
+        optional il2cpp_method_get_declaring_type: fn(*mut Il2CppMethod) -> *mut Il2CppClass,
+        required il2cpp_method_get_name: fn(*mut Il2CppMethod) -> *const c_char,
+        optional il2cpp_method_get_from_reflection: fn(*mut Il2CppReflectionMethod) -> *mut Il2CppMethod,
+        optional il2cpp_method_get_object: fn(*mut Il2CppMethod, *mut Il2CppClass) -> *mut Il2CppReflectionMethod,
+        optional il2cpp_method_is_generic: fn(*mut Il2CppMethod) -> bool,
+        optional il2cpp_method_is_inflated: fn(*mut Il2CppMethod) -> bool,
+        optional il2cpp_method_is_instance: fn(*mut Il2CppMethod) -> bool,
+        required il2cpp_method_get_param_count: fn(*mut Il2CppMethod) -> u32,
+        required il2cpp_method_get_param: fn(*mut Il2CppMethod, u32) -> *mut Il2CppType,
+        required il2cpp_method_get_class: fn(*mut Il2CppMethod) -> *mut Il2CppClass,
+        optional il2cpp_method_has_attribute: fn(*mut Il2CppMethod, *mut Il2CppClass) -> bool,
+        optional il2cpp_method_get_flags: fn(*mut Il2CppMethod, *mut u32) -> u32,
+        optional il2cpp_method_get_token: fn(*mut Il2CppMethod) -> u32,
+        optional il2cpp_method_get_param_name: fn(*mut Il2CppMethod, u32) -> *const c_char,
+
+        // property
+        optional il2cpp_property_get_flags: fn(*mut Il2CppProperty) -> u32,
+        required il2cpp_property_get_get_method: fn(*mut Il2CppProperty) -> *mut Il2CppMethod,
+        required il2cpp_property_get_set_method: fn(*mut Il2CppProperty) -> *mut Il2CppMethod,
+        required il2cpp_property_get_name: fn(*mut Il2CppProperty) -> *const c_char,
+        optional il2cpp_property_get_parent: fn(*mut Il2CppProperty) -> *mut Il2CppClass,
+
+        // object
+        required il2cpp_object_get_class: fn(*mut Il2CppObject) -> *mut Il2CppClass,
+        optional il2cpp_object_get_size: fn(*mut Il2CppObject) -> u32,
+        required il2cpp_object_get_virtual_method: fn(*mut Il2CppObject, *mut Il2CppMethod) -> *mut Il2CppMethod,
+        required il2cpp_object_new: fn(*mut Il2CppClass) -> *mut Il2CppObject,
+        required il2cpp_object_unbox: fn(*mut Il2CppObject) -> *mut c_void,
+        required il2cpp_value_box: fn(*mut Il2CppClass, *mut c_void) -> *mut Il2CppObject,
+
+        // monitor
+        optional il2cpp_monitor_enter: fn(*mut Il2CppObject),
+        optional il2cpp_monitor_try_enter: fn(*mut Il2CppObject, u32) -> bool,
+        optional il2cpp_monitor_exit: fn(*mut Il2CppObject),
+        optional il2cpp_monitor_pulse: fn(*mut Il2CppObject),
+        optional il2cpp_monitor_pulse_all: fn(*mut Il2CppObject),
+        optional il2cpp_monitor_wait: fn(*mut Il2CppObject),
+        optional il2cpp_monitor_try_wait: fn(*mut Il2CppObject, u32) -> bool,
+
+        // runtime
+        required il2cpp_runtime_invoke: fn(*mut Il2CppMethod, *mut Il2CppObject, *mut *mut c_void, *mut *mut Il2CppException) -> *mut Il2CppObject,
+        optional il2cpp_runtime_invoke_convert_args: fn(*mut Il2CppMethod, *mut Il2CppObject, *mut *mut Il2CppObject, c_int, *mut *mut Il2CppException) -> *mut Il2CppObject,
+        optional il2cpp_runtime_class_init: fn(*mut Il2CppClass),
+        optional il2cpp_runtime_object_init: fn(*mut Il2CppObject),
+        optional il2cpp_runtime_object_init_exception: fn(*mut Il2CppObject, *mut *mut Il2CppException),
+        optional il2cpp_runtime_unhandled_exception_policy_set: fn(c_int),
+
+        // string
+        required il2cpp_string_length: fn(*mut Il2CppString) -> i32,
+        required il2cpp_string_chars: fn(*mut Il2CppString) -> *mut u16,
+        required il2cpp_string_new: fn(*const c_char) -> *mut Il2CppString,
+        optional il2cpp_string_new_len: fn(*const c_char, u32) -> *mut Il2CppString,
+        required il2cpp_string_new_utf16: fn(*const u16, i32) -> *mut Il2CppString,
+        optional il2cpp_string_new_wrapper: fn(*const c_char) -> *mut Il2CppString,
+        optional il2cpp_string_intern: fn(*mut Il2CppString) -> *mut Il2CppString,
+        optional il2cpp_string_is_interned: fn(*mut Il2CppString) -> *mut Il2CppString,
+
+        // thread
+        /// returns the current thread
+        required il2cpp_thread_current: fn() -> *mut Il2CppThread,
+        required il2cpp_thread_attach: fn(*mut Il2CppDomain) -> *mut Il2CppThread,
+        required il2cpp_thread_detach: fn(*mut Il2CppThread),
+        optional il2cpp_thread_get_all_attached_threads: fn(*mut usize) -> *mut *mut Il2CppThread,
+        optional il2cpp_is_vm_thread: fn(*mut Il2CppThread) -> bool,
+
+        // type
+        optional il2cpp_type_get_object: fn(*mut Il2CppType) -> *mut Il2CppReflectionType,
+        optional il2cpp_type_get_type: fn(*mut Il2CppType) -> c_int,
+        optional il2cpp_type_get_class_or_element_class: fn(*mut Il2CppType) -> *mut Il2CppClass,
+        required il2cpp_type_get_name: fn(*mut Il2CppType) -> *mut c_char,
+        optional il2cpp_type_is_byref: fn(*mut Il2CppType) -> bool,
+        optional il2cpp_type_get_attrs: fn(*mut Il2CppType) -> u32,
+        optional il2cpp_type_equals: fn(*mut Il2CppType, *mut Il2CppType) -> bool,
+        optional il2cpp_type_get_assembly_qualified_name: fn(*mut Il2CppType) -> *mut c_char,
+        optional il2cpp_type_is_static: fn(*mut Il2CppType) -> bool,
+        optional il2cpp_type_is_pointer_type: fn(*mut Il2CppType) -> bool,
+
+        // image
+        optional il2cpp_image_get_assembly: fn(*mut Il2CppImage) -> *mut Il2CppAssembly,
+        required il2cpp_image_get_name: fn(*mut Il2CppImage) -> *const c_char,
+        required il2cpp_image_get_filename: fn(*mut Il2CppImage) -> *const c_char,
+        optional il2cpp_image_get_entry_point: fn(*mut Il2CppImage) -> *mut Il2CppMethod,
+        optional il2cpp_image_get_class_count: fn(*mut Il2CppImage) -> usize,
+        optional il2cpp_image_get_class: fn(*mut Il2CppImage, usize) -> *mut Il2CppClass,
+
+        // custom attributes
+        optional il2cpp_custom_attrs_has_attr: fn(*mut Il2CppCustomAttrInfo, *mut Il2CppClass) -> bool,
+        optional il2cpp_custom_attrs_from_class: fn(*mut Il2CppClass) -> *mut Il2CppCustomAttrInfo,
+        optional il2cpp_custom_attrs_from_method: fn(*mut Il2CppMethod) -> *mut Il2CppCustomAttrInfo,
+        optional il2cpp_custom_attrs_get_attr: fn(*mut Il2CppCustomAttrInfo, *mut Il2CppClass) -> *mut Il2CppObject,
+        optional il2cpp_custom_attrs_construct: fn(*mut Il2CppCustomAttrInfo) -> *mut Il2CppArray,
+        optional il2cpp_custom_attrs_free: fn(*mut Il2CppCustomAttrInfo),
 
-impl Il2CppExports {
-    /// looks up and returns all methods from il2cpp
-    pub fn new(lib: &NativeLibrary) -> Result<Il2CppExports, LibError> {
-        Ok(Il2CppExports {
-            il2cpp_init: Some(lib.sym("il2cpp_init")?),
-            il2cpp_thread_current: Some(lib.sym("il2cpp_thread_current")?),
-            il2cpp_runtime_invoke: Some(lib.sym("il2cpp_runtime_invoke")?),
-            il2cpp_method_get_name: Some(lib.sym("il2cpp_method_get_name")?),
-            il2cpp_thread_attach: Some(lib.sym("il2cpp_thread_attach")?),
-            il2cpp_domain_get: Some(lib.sym("il2cpp_domain_get")?),
-            il2cpp_add_internal_call: Some(lib.sym("il2cpp_add_internal_call")?),
-        })
+        // misc
+        optional il2cpp_register_log_callback: fn(extern "C" fn(*const c_char)),
+        optional il2cpp_is_debugger_attached: fn() -> bool,
+        optional il2cpp_set_default_thread_affinity: fn(i64),
     }
 }
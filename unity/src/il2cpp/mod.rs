@@ -1,23 +1,30 @@
 //! TODO
 
-use std::{error, path::PathBuf, ffi::CString};
-
-use thiserror::Error;
+use std::{convert::Infallible, path::PathBuf, ffi::{CString, CStr, c_void, c_char, c_int}, slice, ptr, mem, sync::{Arc, Mutex, PoisonError, atomic::{AtomicPtr, Ordering}}, collections::BTreeMap};
 
 use crate::{
     join_dll_path,
-    libs::{self, NativeLibrary, NativeMethod}, runtime::{Runtime, RuntimeError, RuntimeType}, common::{thread::UnityThread, domain::UnityDomain, method::MethodPointer}, mono::AssemblyHookType,
+    libs::{self, NativeLibrary, NativeMethod, ExportReport, LibError}, runtime::{get_runtime, Runtime, RuntimeError, RuntimeType, LookupKind}, common::{callback, gc_handle::GcHandleKind, thread::UnityThread, domain::UnityDomain, init::{self, InitCallback}, internal_call, method::MethodPointer, string::UnityString, class::UnityClass, exception::ManagedException, method::UnityMethod, object::{self, UnityObject}, assembly::UnityAssembly, image::UnityImage, field::UnityField, property::UnityProperty, ty::UnityType},
+    mono::{AssemblyHookType, types::{AssemblyName}},
+    utils::{ffi::c_str_to_string, path::PathError},
 };
 
-use self::{exports::Il2CppExports, types::Il2CppThread};
+use self::{assembly_hook::Stage, exports::Il2CppExports, types::{Il2CppException, Il2CppMethod}};
 
+mod assembly_hook;
 pub mod exports;
 pub mod types;
 
+/// assembly names handed out by `get_assembly_name`, keyed by assembly
+static ASSEMBLY_NAMES: Mutex<BTreeMap<usize, CString>> = Mutex::new(BTreeMap::new());
+
 #[derive(Debug, Clone)]
 pub struct Il2Cpp {
     pub game_assembly: NativeLibrary,
     pub exports: Il2CppExports,
+    pub export_report: ExportReport,
+    /// the thread passed to `set_main_thread`
+    main_thread: Arc<AtomicPtr<c_void>>,
 }
 
 impl Il2Cpp {
@@ -25,26 +32,89 @@ impl Il2Cpp {
         let game_assembly_path = join_dll_path!(base_path, "GameAssembly");
 
         if !game_assembly_path.exists() {
-            return Err(RuntimeError::GameAssemblyNotFound);
+            return Err(PathError::GameAssemblyNotFound.into());
         }
 
         let lib = libs::load_lib(&game_assembly_path)?;
 
-        let exports = Il2CppExports::new(&lib)?;
+        let (exports, export_report) = Il2CppExports::resolve(&lib);
+
+        if !export_report.is_supported() {
+            return Err(LibError::MissingExports(export_report).into());
+        }
 
         let il2cpp = Il2Cpp {
             game_assembly: lib,
             exports,
+            export_report,
+            main_thread: Arc::default(),
         };
         Ok(il2cpp)
     }
+
+    /// records the current thread as the main thread, unless one was set already
+    ///
+    /// `il2cpp_init` runs on unity's main thread, so its detour calls this.
+    fn record_main_thread(&self) {
+        if let Ok(thread) = self.get_current_thread() {
+            let _ = self.main_thread.compare_exchange(ptr::null_mut(), thread.inner, Ordering::AcqRel, Ordering::Acquire);
+        }
+    }
+
+    /// frees memory handed out by il2cpp, such as the result of `il2cpp_type_get_name`
+    pub fn free(&self, ptr: *mut c_void) {
+        if ptr.is_null() {
+            return;
+        }
+
+        if let Some(function) = &self.exports.il2cpp_free {
+            function(ptr);
+        }
+    }
+
+    /// reads a thrown exception through its `Message` and `StackTrace` properties
+    fn read_exception(&self, exception: *mut Il2CppException) -> ManagedException {
+        let class = self.exports
+            .il2cpp_object_get_class
+            .as_ref()
+            .map(|function| UnityClass { inner: function(exception.cast()).cast() });
+
+        ManagedException {
+            class: ManagedException::class_name(self, class),
+            message: self.read_exception_property(exception, "Message").unwrap_or_default(),
+            stack_trace: self.read_exception_property(exception, "StackTrace").unwrap_or_default(),
+        }
+    }
+
+    /// invokes a string getter on an exception, ignoring anything it throws itself
+    fn read_exception_property(&self, exception: *mut Il2CppException, name: &str) -> Option<String> {
+        let object_get_class = self.exports.il2cpp_object_get_class.as_ref()?;
+        let invoke = self.exports.il2cpp_runtime_invoke.as_ref()?;
+
+        let class = UnityClass { inner: object_get_class(exception.cast()).cast() };
+        let property = self.get_property(&class, name).ok()?;
+        let getter = self.get_property_get_method(&property).ok()??;
+
+        let mut inner_exception = ptr::null_mut();
+        let result = invoke(getter.inner.cast(), exception.cast(), ptr::null_mut(), &mut inner_exception);
+
+        if result.is_null() || !inner_exception.is_null() {
+            return None;
+        }
+
+        self.string_to_utf8(&UnityString { inner: result.cast() }).ok()
+    }
 }
 
 impl Runtime for Il2Cpp {
-    fn get_type(&self) -> RuntimeType {
+    fn get_type(&self) -> RuntimeType<'_> {
         RuntimeType::Il2Cpp(self)
     }
 
+    fn get_export_report(&self) -> &ExportReport {
+        &self.export_report
+    }
+
     fn get_export_ptr(&self, name: &str) -> Result<MethodPointer, RuntimeError> {
         let function: NativeMethod<fn()> = self.game_assembly.sym(name)?;
 
@@ -55,12 +125,24 @@ impl Runtime for Il2Cpp {
         Ok(function.inner)
     }
 
+    fn on_initialized(&self, callback: InitCallback) -> Result<(), RuntimeError> {
+        // il2cpp_domain_get creates the domain if it's missing, but the corlib is only loaded by il2cpp_init
+        let current = || self.get_corlib().and_then(|_| self.get_domain()).ok();
+
+        init::register(callback, current, || {
+            let function = self.exports.il2cpp_init.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_init"))?;
+
+            unsafe { function.hook(init_detour as MethodPointer) }
+        })
+    }
+
     fn get_current_thread(&self) -> Result<UnityThread, RuntimeError> {
-        let function = &self.exports.clone().il2cpp_thread_current.ok_or(RuntimeError::MissingFunction("il2cpp_thread_current"))?;
+        let function = self.exports.il2cpp_thread_current.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_thread_current"))?;
         let thread = function();
 
+        // null on threads il2cpp doesn't know
         if thread.is_null() {
-            return Err(RuntimeError::ReturnedNull("il2cpp_thread_current"));
+            return Err(RuntimeError::ThreadNotAttached);
         }
 
         Ok(UnityThread { 
@@ -68,30 +150,42 @@ impl Runtime for Il2Cpp {
         })
     }
 
-    /// this function doesn't exist in il2cpp, it just forwards to il2cpp_thread_attach
+    /// il2cpp has no notion of a main thread, so it's only recorded
+    ///
+    /// if [`on_initialized`](Runtime::on_initialized) hooked `il2cpp_init`, the thread that ran it is recorded by default.
     fn set_main_thread(&self, thread: UnityThread) -> Result<(), RuntimeError> {
-        let function = &self.exports.clone().il2cpp_thread_attach.ok_or(RuntimeError::MissingFunction("il2cpp_thread_attach"))?;
-
         if thread.inner.is_null() {
-            return Err(RuntimeError::ReturnedNull("il2cpp_thread_attach").into());
+            return Err(RuntimeError::NullPointer("thread"));
         }
 
-        function(thread.inner.cast());
+        self.main_thread.store(thread.inner, Ordering::Release);
 
         Ok(())
     }
 
-    fn attach_to_thread(&self, thread: UnityDomain) -> Result<UnityThread, RuntimeError> {
-        let function = &self.exports.clone().il2cpp_thread_attach.ok_or(RuntimeError::MissingFunction("il2cpp_thread_attach"))?;
+    fn main_thread(&self) -> Result<UnityThread, RuntimeError> {
+        let thread = self.main_thread.load(Ordering::Acquire);
 
-        if thread.inner.is_null() {
-            return Err(RuntimeError::ReturnedNull("il2cpp_thread_attach").into());
+        if thread.is_null() {
+            return Err(RuntimeError::MainThreadNotSet);
+        }
+
+        Ok(UnityThread {
+            inner: thread,
+        })
+    }
+
+    fn attach_to_thread(&self, domain: UnityDomain) -> Result<UnityThread, RuntimeError> {
+        let function = self.exports.il2cpp_thread_attach.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_thread_attach"))?;
+
+        if domain.inner.is_null() {
+            return Err(RuntimeError::NullPointer("domain"));
         }
 
-        let thread = function(thread.inner.cast());
+        let thread = function(domain.inner.cast());
 
         if thread.is_null() {
-            return Err(RuntimeError::ReturnedNull("il2cpp_thread_attach").into());
+            return Err(RuntimeError::ReturnedNull("il2cpp_thread_attach"));
         }
 
         Ok(UnityThread {
@@ -99,13 +193,23 @@ impl Runtime for Il2Cpp {
         })
     }
 
-    fn add_internal_call(&self, name: String, func: MethodPointer) -> Result<(), RuntimeError> {
-        let function = &self.exports.clone().il2cpp_add_internal_call.ok_or(RuntimeError::MissingFunction("il2cpp_add_internal_call"))?;
+    fn detach_from_thread(&self, thread: UnityThread) -> Result<(), RuntimeError> {
+        let function = self.exports.il2cpp_thread_detach.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_thread_detach"))?;
 
-        if name.is_empty() {
-            return Err(RuntimeError::EmptyString);
+        if thread.inner.is_null() {
+            return Err(RuntimeError::NullPointer("thread"));
         }
 
+        function(thread.inner.cast());
+
+        Ok(())
+    }
+
+    fn add_internal_call(&self, name: String, func: MethodPointer) -> Result<(), RuntimeError> {
+        let function = self.exports.il2cpp_add_internal_call.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_add_internal_call"))?;
+
+        internal_call::validate_name(&name)?;
+
         if func.is_null() {
             return Err(RuntimeError::NullPointer("func"));
         }
@@ -117,12 +221,28 @@ impl Runtime for Il2Cpp {
         Ok(())
     }
 
-    fn install_assembly_hook(&self, hook_type: AssemblyHookType, func: MethodPointer) -> Result<(), RuntimeError> {
-        return Err(RuntimeError::NotImplemented("install_assembly_hook are mono only functions"));
+    fn install_assembly_hook(&self, hook_type: AssemblyHookType) -> Result<(), RuntimeError> {
+        // il2cpp has no assembly hooks, but all assemblies are there from the start,
+        // so loads are replayed once it's initialized, and lookups go through `il2cpp_domain_assembly_open`
+        let resolve = |stage, resolver| {
+            let function = self.exports.il2cpp_domain_assembly_open.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_domain_assembly_open"))?;
+
+            assembly_hook::add_resolver(function, stage, resolver)
+        };
+
+        match hook_type {
+            AssemblyHookType::Preload(resolver) => resolve(Stage::Preload, resolver),
+            AssemblyHookType::Search(resolver) => resolve(Stage::Search, resolver),
+            AssemblyHookType::Load(on_load) => self.on_initialized(Box::new(move |domain| {
+                for assembly in domain.assemblies().unwrap_or_default() {
+                    callback::catch("assembly load hook", || on_load(&assembly));
+                }
+            })),
+        }
     }
-    
+
     fn get_domain(&self) -> Result<UnityDomain, RuntimeError> {
-        let function = &self.exports.clone().il2cpp_domain_get.ok_or(RuntimeError::MissingFunction("il2cpp_domain_get"))?;
+        let function = self.exports.il2cpp_domain_get.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_domain_get"))?;
 
         let domain = function();
 
@@ -134,4 +254,1061 @@ impl Runtime for Il2Cpp {
             inner: domain.cast(),
         })
     }
+
+    fn new_string(&self, value: &str) -> Result<UnityString, RuntimeError> {
+        let function = self.exports.il2cpp_string_new.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_string_new"))?;
+
+        let value = CString::new(value)?;
+
+        let string = function(value.as_ptr());
+
+        if string.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_string_new"));
+        }
+
+        Ok(UnityString {
+            inner: string.cast(),
+        })
+    }
+
+    fn new_string_utf16(&self, value: &[u16]) -> Result<UnityString, RuntimeError> {
+        let function = self.exports.il2cpp_string_new_utf16.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_string_new_utf16"))?;
+
+        let length = i32::try_from(value.len()).map_err(|_| RuntimeError::InvalidString("too long"))?;
+
+        let string = function(value.as_ptr(), length);
+
+        if string.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_string_new_utf16"));
+        }
+
+        Ok(UnityString {
+            inner: string.cast(),
+        })
+    }
+
+    /// il2cpp has no utf8 conversion of its own, so this decodes the UTF-16 chars
+    fn string_to_utf8(&self, string: &UnityString) -> Result<String, RuntimeError> {
+        let chars = self.string_to_utf16(string)?;
+
+        String::from_utf16(&chars).map_err(|_| RuntimeError::InvalidString("not valid UTF-16"))
+    }
+
+    fn string_to_utf16(&self, string: &UnityString) -> Result<Vec<u16>, RuntimeError> {
+        let chars_function = self.exports.il2cpp_string_chars.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_string_chars"))?;
+        let length_function = self.exports.il2cpp_string_length.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_string_length"))?;
+
+        if string.inner.is_null() {
+            return Err(RuntimeError::NullPointer("string"));
+        }
+
+        let chars = chars_function(string.inner.cast());
+        let length = length_function(string.inner.cast());
+
+        if chars.is_null() || length < 0 {
+            return Err(RuntimeError::ReturnedNull("il2cpp_string_chars"));
+        }
+
+        Ok(unsafe { slice::from_raw_parts(chars, length as usize) }.to_vec())
+    }
+
+    fn get_assemblies(&self, domain: &UnityDomain) -> Result<Vec<UnityAssembly>, RuntimeError> {
+        let function = self.exports.il2cpp_domain_get_assemblies.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_domain_get_assemblies"))?;
+
+        if domain.inner.is_null() {
+            return Err(RuntimeError::NullPointer("domain"));
+        }
+
+        let mut size = 0;
+        let assemblies = function(domain.inner.cast(), &mut size);
+
+        if assemblies.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_domain_get_assemblies"));
+        }
+
+        Ok(unsafe { slice::from_raw_parts(assemblies, size) }
+            .iter()
+            .map(|assembly| UnityAssembly {
+                inner: assembly.cast(),
+            })
+            .collect())
+    }
+
+    fn open_assembly(&self, domain: &UnityDomain, path: &str) -> Result<UnityAssembly, RuntimeError> {
+        let function = self.exports.il2cpp_domain_assembly_open.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_domain_assembly_open"))?;
+
+        if domain.inner.is_null() {
+            return Err(RuntimeError::NullPointer("domain"));
+        }
+
+        let assembly_path = CString::new(path)?;
+        let assembly = function(domain.inner.cast(), assembly_path.as_ptr());
+
+        if assembly.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Assembly, path));
+        }
+
+        Ok(UnityAssembly {
+            inner: assembly.cast(),
+        })
+    }
+
+    fn get_assembly_image(&self, assembly: &UnityAssembly) -> Result<UnityImage, RuntimeError> {
+        let function = self.exports.il2cpp_assembly_get_image.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_assembly_get_image"))?;
+
+        if assembly.inner.is_null() {
+            return Err(RuntimeError::NullPointer("assembly"));
+        }
+
+        let image = function(assembly.inner.cast());
+
+        if image.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_assembly_get_image"));
+        }
+
+        Ok(UnityImage {
+            inner: image.cast(),
+        })
+    }
+
+    /// il2cpp has no assembly name api, so only the name is filled in, taken from the image
+    fn get_assembly_name(&self, assembly: &UnityAssembly) -> Result<AssemblyName, RuntimeError> {
+        let image = self.get_assembly_image(assembly)?;
+        let image_name = self.get_image_name(&image)?;
+
+        // the name has to outlive the returned struct, so keep one copy per assembly around
+        let mut names = ASSEMBLY_NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+        let name = match names.get(&(assembly.inner as usize)) {
+            Some(name) => name.as_ptr(),
+            None => {
+                let stem = image_name.strip_suffix(".dll").unwrap_or(&image_name);
+                let name = CString::new(stem)?;
+                let ptr = name.as_ptr();
+                names.insert(assembly.inner as usize, name);
+                ptr
+            }
+        };
+
+        Ok(AssemblyName {
+            name: name as *mut c_char,
+            culture: ptr::null_mut(),
+            hash_value: ptr::null_mut(),
+            public_key: ptr::null_mut(),
+            public_key_token: [0; 17],
+            hash_alg: 0,
+            hash_len: 0,
+            flags: 0,
+            major: 0,
+            minor: 0,
+            build: 0,
+            revision: 0,
+            arch: 0,
+        })
+    }
+
+    fn get_image_name(&self, image: &UnityImage) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_image_get_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_image_get_name"))?;
+
+        if image.inner.is_null() {
+            return Err(RuntimeError::NullPointer("image"));
+        }
+
+        c_str_to_string(function(image.inner.cast()), "il2cpp_image_get_name")
+    }
+
+    fn get_image_filename(&self, image: &UnityImage) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_image_get_filename.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_image_get_filename"))?;
+
+        if image.inner.is_null() {
+            return Err(RuntimeError::NullPointer("image"));
+        }
+
+        c_str_to_string(function(image.inner.cast()), "il2cpp_image_get_filename")
+    }
+
+    fn get_class_from_image(&self, image: &UnityImage, namespace: &str, name: &str) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.il2cpp_class_from_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_from_name"))?;
+
+        if image.inner.is_null() {
+            return Err(RuntimeError::NullPointer("image"));
+        }
+
+        let namespace_name = CString::new(namespace)?;
+        let class_name = CString::new(name)?;
+
+        let class = function(image.inner.cast(), namespace_name.as_ptr(), class_name.as_ptr());
+
+        if class.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Class, format!("{}.{}", namespace, name)));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    fn get_class_name(&self, class: &UnityClass) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        c_str_to_string(function(class.inner.cast()), "il2cpp_class_get_name")
+    }
+
+    fn get_class_namespace(&self, class: &UnityClass) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_namespace.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_namespace"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        c_str_to_string(function(class.inner.cast()), "il2cpp_class_get_namespace")
+    }
+
+    fn next_class_method(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityMethod>, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_methods.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_methods"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityMethod {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_class_field(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityField>, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_fields.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_fields"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityField {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_class_property(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityProperty>, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_properties.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_properties"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityProperty {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_nested_type(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityClass>, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_nested_types.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_nested_types"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityClass {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_class_interface(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityClass>, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_interfaces.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_interfaces"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityClass {
+            inner: member.cast(),
+        }))
+    }
+
+    fn get_class_parent(&self, class: &UnityClass) -> Result<Option<UnityClass>, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_parent.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_parent"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let parent = function(class.inner.cast());
+
+        if parent.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityClass {
+            inner: parent.cast(),
+        }))
+    }
+
+    fn get_class_flags(&self, class: &UnityClass) -> Result<i32, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_flags.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_flags"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        Ok(function(class.inner.cast()))
+    }
+
+    fn is_class_generic(&self, class: &UnityClass) -> Result<bool, RuntimeError> {
+        let function = self.exports.il2cpp_class_is_generic.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_is_generic"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        // only true for definitions, instances like List<int> are inflated
+        let is_inflated = match self.exports.il2cpp_class_is_inflated.as_ref() {
+            Some(is_inflated) => is_inflated(class.inner.cast()),
+            None => false,
+        };
+
+        Ok(function(class.inner.cast()) || is_inflated)
+    }
+
+    fn get_method(&self, class: &UnityClass, name: &str, param_count: i32) -> Result<UnityMethod, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_method_from_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_method_from_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let method_name = CString::new(name)?;
+
+        let method = function(class.inner.cast(), method_name.as_ptr(), param_count);
+
+        if method.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Method, name));
+        }
+
+        Ok(UnityMethod {
+            inner: method.cast(),
+        })
+    }
+
+    fn get_method_name(&self, method: &UnityMethod) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_method_get_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_method_get_name"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        c_str_to_string(function(method.inner.cast()), "il2cpp_method_get_name")
+    }
+
+    fn get_method_class(&self, method: &UnityMethod) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.il2cpp_method_get_class.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_method_get_class"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let class = function(method.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_method_get_class"));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    fn get_method_flags(&self, method: &UnityMethod) -> Result<i32, RuntimeError> {
+        let function = self.exports.il2cpp_method_get_flags.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_method_get_flags"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        Ok(function(method.inner.cast(), ptr::null_mut()) as i32)
+    }
+
+    fn get_method_param_count(&self, method: &UnityMethod) -> Result<u32, RuntimeError> {
+        let function = self.exports.il2cpp_method_get_param_count.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_method_get_param_count"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        Ok(function(method.inner.cast()))
+    }
+
+    fn get_method_return_type(&self, method: &UnityMethod) -> Result<UnityType, RuntimeError> {
+        let function = self.exports.il2cpp_method_get_return_type.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_method_get_return_type"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let ty = function(method.inner.cast());
+
+        if ty.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_method_get_return_type"));
+        }
+
+        Ok(UnityType {
+            inner: ty.cast(),
+        })
+    }
+
+    fn get_method_param_types(&self, method: &UnityMethod) -> Result<Vec<UnityType>, RuntimeError> {
+        let function = self.exports.il2cpp_method_get_param.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_method_get_param"))?;
+
+        (0..self.get_method_param_count(method)?).map(|index| {
+            let ty = function(method.inner.cast(), index);
+
+            if ty.is_null() {
+                return Err(RuntimeError::ReturnedNull("il2cpp_method_get_param"));
+            }
+
+            Ok(UnityType {
+                inner: ty.cast(),
+            })
+        }).collect()
+    }
+
+    fn get_method_param_names(&self, method: &UnityMethod) -> Result<Vec<String>, RuntimeError> {
+        let function = self.exports.il2cpp_method_get_param_name.as_ref();
+
+        (0..self.get_method_param_count(method)?).map(|index| {
+            // stripped metadata may not have names, and old unity versions can't look them up
+            let name = function.map_or(ptr::null(), |function| function(method.inner.cast(), index));
+
+            match name.is_null() {
+                true => Ok(format!("arg{}", index)),
+                false => c_str_to_string(name, "il2cpp_method_get_param_name"),
+            }
+        }).collect()
+    }
+
+    /// il2cpp compiles ahead of time, so this is read straight from the `MethodInfo`
+    fn get_method_pointer(&self, method: &UnityMethod) -> Result<MethodPointer, RuntimeError> {
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let pointer = unsafe { (*method.inner.cast::<Il2CppMethod>()).method_pointer };
+
+        if pointer.is_null() {
+            return Err(RuntimeError::ReturnedNull("MethodInfo::methodPointer"));
+        }
+
+        Ok(pointer)
+    }
+
+    fn new_exception(&self, namespace: &str, name: &str, message: &CStr) -> Result<UnityObject, RuntimeError> {
+        let get_corlib = self.exports.il2cpp_get_corlib.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_get_corlib"))?;
+        let from_name = self.exports.il2cpp_exception_from_name_msg.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_exception_from_name_msg"))?;
+
+        let corlib = get_corlib();
+
+        if corlib.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_get_corlib"));
+        }
+
+        let namespace_str = CString::new(namespace)?;
+        let name_str = CString::new(name)?;
+
+        let exception = from_name(corlib, namespace_str.as_ptr(), name_str.as_ptr(), message.as_ptr());
+
+        if exception.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Class, format!("{}.{}", namespace, name)));
+        }
+
+        Ok(UnityObject {
+            inner: exception.cast(),
+        })
+    }
+
+    unsafe fn raise_exception(&self, exception: UnityObject) -> Result<Infallible, RuntimeError> {
+        let raise = self.exports.il2cpp_raise_exception.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_raise_exception"))?;
+
+        if exception.inner.is_null() {
+            return Err(RuntimeError::NullPointer("exception"));
+        }
+
+        raise(exception.inner.cast());
+
+        Err(RuntimeError::ReturnedNull("il2cpp_raise_exception"))
+    }
+
+    fn invoke_method(&self, method: &UnityMethod, instance: Option<&UnityObject>, args: &mut [*mut c_void]) -> Result<Option<UnityObject>, RuntimeError> {
+        let function = self.exports.il2cpp_runtime_invoke.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_runtime_invoke"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let instance = instance.map_or(ptr::null_mut(), |instance| instance.inner);
+        let params = match args.is_empty() {
+            true => ptr::null_mut(),
+            false => args.as_mut_ptr(),
+        };
+
+        let mut exception = ptr::null_mut();
+        let result = function(method.inner.cast(), instance.cast(), params, &mut exception);
+
+        if !exception.is_null() {
+            return Err(RuntimeError::ManagedException(self.read_exception(exception)));
+        }
+
+        match result.is_null() {
+            true => Ok(None),
+            false => Ok(Some(UnityObject { inner: result.cast() })),
+        }
+    }
+
+    fn get_field(&self, class: &UnityClass, name: &str) -> Result<UnityField, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_field_from_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_field_from_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let field_name = CString::new(name)?;
+
+        let field = function(class.inner.cast(), field_name.as_ptr());
+
+        if field.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Field, name));
+        }
+
+        Ok(UnityField {
+            inner: field.cast(),
+        })
+    }
+
+    fn get_field_name(&self, field: &UnityField) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_field_get_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_get_name"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        c_str_to_string(function(field.inner.cast()), "il2cpp_field_get_name")
+    }
+
+    fn get_field_offset(&self, field: &UnityField) -> Result<usize, RuntimeError> {
+        let function = self.exports.il2cpp_field_get_offset.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_get_offset"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        Ok(function(field.inner.cast()))
+    }
+
+    fn get_field_type(&self, field: &UnityField) -> Result<UnityType, RuntimeError> {
+        let function = self.exports.il2cpp_field_get_type.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_get_type"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        let ty = function(field.inner.cast());
+
+        if ty.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_field_get_type"));
+        }
+
+        Ok(UnityType {
+            inner: ty.cast(),
+        })
+    }
+
+    fn get_field_flags(&self, field: &UnityField) -> Result<i32, RuntimeError> {
+        let function = self.exports.il2cpp_field_get_flags.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_get_flags"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        Ok(function(field.inner.cast()))
+    }
+
+    fn get_field_value(&self, field: &UnityField, instance: &UnityObject, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.il2cpp_field_get_value.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_get_value"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        if instance.inner.is_null() {
+            return Err(RuntimeError::NullPointer("instance"));
+        }
+
+        function(instance.inner.cast(), field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn set_field_value(&self, field: &UnityField, instance: &UnityObject, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.il2cpp_field_set_value.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_set_value"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        if instance.inner.is_null() {
+            return Err(RuntimeError::NullPointer("instance"));
+        }
+
+        function(instance.inner.cast(), field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn get_static_field_value(&self, field: &UnityField, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.il2cpp_field_static_get_value.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_static_get_value"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        function(field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn set_static_field_value(&self, field: &UnityField, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.il2cpp_field_static_set_value.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_field_static_set_value"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        function(field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn get_type_name(&self, ty: &UnityType) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_type_get_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_type_get_name"))?;
+
+        if ty.inner.is_null() {
+            return Err(RuntimeError::NullPointer("ty"));
+        }
+
+        let name = function(ty.inner.cast());
+        let result = c_str_to_string(name, "il2cpp_type_get_name");
+
+        self.free(name.cast());
+
+        result
+    }
+
+    fn get_type_class(&self, ty: &UnityType) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.il2cpp_class_from_type.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_from_type"))?;
+
+        if ty.inner.is_null() {
+            return Err(RuntimeError::NullPointer("ty"));
+        }
+
+        let class = function(ty.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_class_from_type"));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    /// il2cpp has no type size api, so this goes through the class of the type
+    fn get_type_size(&self, ty: &UnityType) -> Result<usize, RuntimeError> {
+        let valuetype_function = self.exports.il2cpp_class_is_valuetype.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_is_valuetype"))?;
+        let size_function = self.exports.il2cpp_class_value_size.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_value_size"))?;
+
+        let class = self.get_type_class(ty)?;
+
+        if !valuetype_function(class.inner.cast()) {
+            return Ok(mem::size_of::<*mut c_void>());
+        }
+
+        let mut align = 0;
+        let size = size_function(class.inner.cast(), &mut align);
+
+        Ok(size as usize)
+    }
+
+    fn get_corlib(&self) -> Result<UnityImage, RuntimeError> {
+        let function = self.exports.il2cpp_get_corlib.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_get_corlib"))?;
+
+        let image = function();
+
+        if image.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_get_corlib"));
+        }
+
+        Ok(UnityImage {
+            inner: image.cast(),
+        })
+    }
+
+    fn is_value_type(&self, class: &UnityClass) -> Result<bool, RuntimeError> {
+        let function = self.exports.il2cpp_class_is_valuetype.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_is_valuetype"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        Ok(function(class.inner.cast()))
+    }
+
+    fn get_class_value_size(&self, class: &UnityClass) -> Result<usize, RuntimeError> {
+        let function = self.exports.il2cpp_class_value_size.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_value_size"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let mut align = 0;
+        let size = function(class.inner.cast(), &mut align);
+
+        Ok(size as usize)
+    }
+
+    fn get_object_class(&self, object: &UnityObject) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.il2cpp_object_get_class.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_object_get_class"))?;
+
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        let class = function(object.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_object_get_class"));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    fn is_instance_of(&self, object: &UnityObject, class: &UnityClass) -> Result<bool, RuntimeError> {
+        let function = self.exports.il2cpp_class_is_assignable_from.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_is_assignable_from"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let object_class = self.get_object_class(object)?;
+
+        Ok(function(class.inner.cast(), object_class.inner.cast()))
+    }
+
+    fn new_object(&self, class: &UnityClass) -> Result<UnityObject, RuntimeError> {
+        let function = self.exports.il2cpp_object_new.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_object_new"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let object = function(class.inner.cast());
+
+        if object.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_object_new"));
+        }
+
+        Ok(UnityObject {
+            inner: object.cast(),
+        })
+    }
+
+    fn box_value(&self, class: &UnityClass, value: *mut c_void) -> Result<UnityObject, RuntimeError> {
+        let function = self.exports.il2cpp_value_box.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_value_box"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        if value.is_null() {
+            return Err(RuntimeError::NullPointer("value"));
+        }
+
+        let object = function(class.inner.cast(), value);
+
+        if object.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_value_box"));
+        }
+
+        Ok(UnityObject {
+            inner: object.cast(),
+        })
+    }
+
+    fn unbox_value(&self, object: &UnityObject) -> Result<*mut c_void, RuntimeError> {
+        let function = self.exports.il2cpp_object_unbox.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_object_unbox"))?;
+
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        let value = function(object.inner.cast());
+
+        if value.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_object_unbox"));
+        }
+
+        Ok(value)
+    }
+
+    fn get_virtual_method(&self, object: &UnityObject, method: &UnityMethod) -> Result<UnityMethod, RuntimeError> {
+        let function = self.exports.il2cpp_object_get_virtual_method.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_object_get_virtual_method"))?;
+
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let method = function(object.inner.cast(), method.inner.cast());
+
+        if method.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_object_get_virtual_method"));
+        }
+
+        Ok(UnityMethod {
+            inner: method.cast(),
+        })
+    }
+
+    fn object_to_string(&self, object: &UnityObject) -> Result<String, RuntimeError> {
+        object::invoke_to_string(self, object)
+    }
+
+    fn new_array(&self, element_class: &UnityClass, len: usize) -> Result<UnityObject, RuntimeError> {
+        let function = self.exports.il2cpp_array_new.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_array_new"))?;
+
+        if element_class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("element_class"));
+        }
+
+        let array = function(element_class.inner.cast(), len);
+
+        if array.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_array_new"));
+        }
+
+        Ok(UnityObject {
+            inner: array.cast(),
+        })
+    }
+
+    fn get_array_length(&self, array: &UnityObject) -> Result<usize, RuntimeError> {
+        let function = self.exports.il2cpp_array_length.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_array_length"))?;
+
+        if array.inner.is_null() {
+            return Err(RuntimeError::NullPointer("array"));
+        }
+
+        Ok(function(array.inner.cast()) as usize)
+    }
+
+    fn get_array_element_size(&self, array: &UnityObject) -> Result<usize, RuntimeError> {
+        let class_function = self.exports.il2cpp_object_get_class.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_object_get_class"))?;
+        let size_function = self.exports.il2cpp_class_array_element_size.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_array_element_size"))?;
+
+        if array.inner.is_null() {
+            return Err(RuntimeError::NullPointer("array"));
+        }
+
+        let class = class_function(array.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("il2cpp_object_get_class"));
+        }
+
+        Ok(size_function(class) as usize)
+    }
+
+    unsafe fn set_array_reference(&self, array: &UnityObject, slot: *mut *mut c_void, value: &UnityObject) -> Result<(), RuntimeError> {
+        if array.inner.is_null() {
+            return Err(RuntimeError::NullPointer("array"));
+        }
+
+        // only builds with an incremental gc export the barrier, the others don't need it
+        match self.exports.il2cpp_gc_wbarrier_set_field.as_ref() {
+            Some(function) => function(array.inner.cast(), slot, value.inner),
+            None => *slot = value.inner,
+        }
+
+        Ok(())
+    }
+
+    fn new_gc_handle(&self, object: &UnityObject, kind: GcHandleKind) -> Result<usize, RuntimeError> {
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        // the handle encodes a table slot, which fits in 32 bits on every version
+        let handle = match kind {
+            GcHandleKind::Weak => {
+                let function = self.exports.il2cpp_gchandle_new_weakref.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_gchandle_new_weakref"))?;
+                function(object.inner.cast(), false)
+            }
+            GcHandleKind::Strong | GcHandleKind::Pinned => {
+                let function = self.exports.il2cpp_gchandle_new.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_gchandle_new"))?;
+                function(object.inner.cast(), kind == GcHandleKind::Pinned)
+            }
+        } as u32 as usize;
+
+        if handle == 0 {
+            return Err(RuntimeError::ReturnedNull("il2cpp_gchandle_new"));
+        }
+
+        Ok(handle)
+    }
+
+    fn get_gc_handle_target(&self, handle: usize) -> Result<Option<UnityObject>, RuntimeError> {
+        let function = self.exports.il2cpp_gchandle_get_target.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_gchandle_get_target"))?;
+
+        let object = function(handle);
+
+        match object.is_null() {
+            true => Ok(None),
+            false => Ok(Some(UnityObject {
+                inner: object.cast(),
+            })),
+        }
+    }
+
+    fn free_gc_handle(&self, handle: usize) -> Result<(), RuntimeError> {
+        let function = self.exports.il2cpp_gchandle_free.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_gchandle_free"))?;
+
+        function(handle);
+
+        Ok(())
+    }
+
+    fn get_property(&self, class: &UnityClass, name: &str) -> Result<UnityProperty, RuntimeError> {
+        let function = self.exports.il2cpp_class_get_property_from_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_class_get_property_from_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let property_name = CString::new(name)?;
+
+        let property = function(class.inner.cast(), property_name.as_ptr());
+
+        if property.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Property, name));
+        }
+
+        Ok(UnityProperty {
+            inner: property.cast(),
+        })
+    }
+
+    fn get_property_name(&self, property: &UnityProperty) -> Result<String, RuntimeError> {
+        let function = self.exports.il2cpp_property_get_name.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_property_get_name"))?;
+
+        if property.inner.is_null() {
+            return Err(RuntimeError::NullPointer("property"));
+        }
+
+        c_str_to_string(function(property.inner.cast()), "il2cpp_property_get_name")
+    }
+
+    fn get_property_get_method(&self, property: &UnityProperty) -> Result<Option<UnityMethod>, RuntimeError> {
+        let function = self.exports.il2cpp_property_get_get_method.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_property_get_get_method"))?;
+
+        if property.inner.is_null() {
+            return Err(RuntimeError::NullPointer("property"));
+        }
+
+        let method = function(property.inner.cast());
+
+        if method.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityMethod {
+            inner: method.cast(),
+        }))
+    }
+
+    fn get_property_set_method(&self, property: &UnityProperty) -> Result<Option<UnityMethod>, RuntimeError> {
+        let function = self.exports.il2cpp_property_get_set_method.as_ref().ok_or(RuntimeError::MissingFunction("il2cpp_property_get_set_method"))?;
+
+        if property.inner.is_null() {
+            return Err(RuntimeError::NullPointer("property"));
+        }
+
+        let method = function(property.inner.cast());
+
+        if method.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityMethod {
+            inner: method.cast(),
+        }))
+    }
+}
+
+/// stands in for `il2cpp_init`, see [`Runtime::on_initialized`]
+///
+/// unlike mono's init, `il2cpp_init` only reports success, so the domain is fetched afterwards
+extern "C" fn init_detour(domain_name: *const c_char) -> c_int {
+    let Some(original) = init::original::<extern "C" fn(*const c_char) -> c_int>() else {
+        return 0;
+    };
+
+    let result = original(domain_name);
+
+    if result != 0 {
+        if let Ok(runtime) = get_runtime() {
+            if let RuntimeType::Il2Cpp(il2cpp) = runtime.get_type() {
+                il2cpp.record_main_thread();
+            }
+
+            if let Ok(domain) = runtime.get_domain() {
+                init::initialized(domain);
+            }
+        }
+    }
+
+    result
 }
@@ -1,17 +1,80 @@
 //! TODO
 
+use std::ffi::c_void;
+
 #[derive(Debug)]
 #[repr(C)]
 pub struct Il2CppDomain {}
 
+/// a method, only the leading fields are stable across il2cpp versions
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppMethod {
+    /// the native code of the method
+    pub method_pointer: *mut c_void,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppClass {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppAssembly {}
+
 #[derive(Debug)]
 #[repr(C)]
-pub struct Il2CppMethod {}
+pub struct Il2CppImage {}
 
 #[derive(Debug)]
 #[repr(C)]
-pub struct Il2CppObject {}
+pub struct Il2CppObject {
+    /// the class
+    pub klass: *mut c_void,
+    /// the monitor
+    pub monitor: *mut c_void,
+}
 
 #[derive(Debug)]
 #[repr(C)]
 pub struct Il2CppThread {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppString {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppArray {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppException {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppField {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppProperty {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppEvent {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppType {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppReflectionType {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppReflectionMethod {}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Il2CppCustomAttrInfo {}
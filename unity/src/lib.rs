@@ -3,6 +3,7 @@
 pub mod runtime;
 
 pub mod common;
+pub mod hook;
 pub mod il2cpp;
 pub mod libs;
 pub mod mono;
@@ -8,19 +8,24 @@
 //!
 //! # Examples
 //!
-//! ```
+//! ```no_run
 //! use std::path::PathBuf;
-//! use std::mem;
 //!
-//! use unity_rs::libs::load_lib;
+//! use unity_rs::libs::{load_lib, NativeMethod};
 //!
+//! # fn main() -> Result<(), unity_rs::libs::LibError> {
 //! let path = PathBuf::from("path/to/lib");
 //! let lib = load_lib(&path)?;
 //!
-//! let func: extern fn() = unsafe { mem::transmute(lib.get_fn_ptr("func_name")?) };
+//! let func: NativeMethod<fn()> = lib.sym("func_name")?;
+//! func();
+//! # Ok(())
+//! # }
+//! ```
 
 use std::{
     ffi::c_void,
+    fmt::{self, Display},
     marker::PhantomData,
     ops::Deref,
     path::{Path, PathBuf},
@@ -30,9 +35,9 @@ use thiserror::Error;
 /// possible library loading errors
 #[derive(Debug, Error)]
 pub enum LibError {
-    /// failed to load library
-    #[error("Failed to load library!")]
-    FailedToLoadLib,
+    /// failed to load library, with the reason given by `dlerror`/`GetLastError`
+    #[error("Failed to load library {}: {reason}", path.display())]
+    FailedToLoadLib { path: PathBuf, reason: String },
 
     /// failed to get lib name
     #[error("Failed to get lib name!")]
@@ -42,12 +47,16 @@ pub enum LibError {
     #[error("Failed to get lib path!")]
     FailedToGetLibPath,
 
-    /// failed to get function pointer
-    #[error("Failed to get function pointer: {0}")]
-    FailedToGetFnPtr(String),
+    /// failed to get function pointer, with the reason given by `dlerror`/`GetLastError`
+    #[error("Failed to get function pointer {name}: {reason}")]
+    FailedToGetFnPtr { name: String, reason: String },
 
     #[error("Failed to create C-String")]
     FailedToCreateCString,
+
+    /// the library lacks exports we can't work without
+    #[error("Unsupported library, {0}")]
+    MissingExports(ExportReport),
 }
 
 /// a representation of a permanently loaded library
@@ -61,17 +70,20 @@ pub struct NativeLibrary {
     pub handle: *mut c_void,
 }
 
+unsafe impl Send for NativeLibrary {}
+unsafe impl Sync for NativeLibrary {}
+
 impl NativeLibrary {
     /// gets a function pointer
     #[cfg(target_os = "linux")]
     pub fn sym<T>(&self, name_str: &str) -> Result<NativeMethod<T>, LibError> {
-
-        let display_string = name_str.to_string();
-
         let name = std::ffi::CString::new(name_str).map_err(|_| LibError::FailedToCreateCString)?;
         let ptr = unsafe { libc::dlsym(self.handle, name.as_ptr()) };
         if ptr.is_null() {
-            return Err(LibError::FailedToGetFnPtr(display_string));
+            return Err(LibError::FailedToGetFnPtr {
+                name: name_str.to_string(),
+                reason: last_error(),
+            });
         }
 
         Ok(NativeMethod {
@@ -85,15 +97,16 @@ impl NativeLibrary {
     pub fn sym<T>(&self, name_str: &str) -> Result<NativeMethod<T>, LibError> {
         use std::ffi::CString;
 
-        let display_string = name_str.to_string();
-
         use winapi::um::libloaderapi::GetProcAddress;
 
         let name = CString::new(name_str).map_err(|_| LibError::FailedToCreateCString)?;
 
         let ptr = unsafe { GetProcAddress(self.handle.cast(), name.as_ptr()) };
         if ptr.is_null() {
-            return Err(LibError::FailedToGetFnPtr(display_string));
+            return Err(LibError::FailedToGetFnPtr {
+                name: name_str.to_string(),
+                reason: last_error(),
+            });
         }
 
         Ok(NativeMethod {
@@ -103,6 +116,23 @@ impl NativeLibrary {
     }
 }
 
+/// the reason the last `dlopen`/`dlsym` call failed
+#[cfg(target_os = "linux")]
+fn last_error() -> String {
+    let error = unsafe { libc::dlerror() };
+
+    match error.is_null() {
+        true => "unknown error".to_string(),
+        false => unsafe { std::ffi::CStr::from_ptr(error) }.to_string_lossy().into_owned(),
+    }
+}
+
+/// the reason the last `LoadLibraryA`/`GetProcAddress` call failed
+#[cfg(target_os = "windows")]
+fn last_error() -> String {
+    std::io::Error::last_os_error().to_string()
+}
+
 /// loads a library permanently
 ///
 /// # Arguments
@@ -120,7 +150,7 @@ impl NativeLibrary {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use std::path::PathBuf;
 ///
 /// use unity_rs::libs::load_lib;
@@ -129,6 +159,7 @@ impl NativeLibrary {
 /// let lib = load_lib(&path);
 ///
 /// assert!(lib.is_ok());
+/// ```
 #[cfg(target_os = "linux")]
 pub fn load_lib<P: AsRef<Path>>(path: P) -> Result<NativeLibrary, LibError> {
     use std::ffi::CString;
@@ -142,7 +173,10 @@ pub fn load_lib<P: AsRef<Path>>(path: P) -> Result<NativeLibrary, LibError> {
     let lib = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_GLOBAL) };
 
     if lib.is_null() {
-        return Err(LibError::FailedToLoadLib);
+        return Err(LibError::FailedToLoadLib {
+            path: path.to_path_buf(),
+            reason: last_error(),
+        });
     }
 
     let lib_name = path
@@ -176,7 +210,7 @@ pub fn load_lib<P: AsRef<Path>>(path: P) -> Result<NativeLibrary, LibError> {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use std::path::PathBuf;
 ///
 /// use unity_rs::libs::load_lib;
@@ -185,6 +219,7 @@ pub fn load_lib<P: AsRef<Path>>(path: P) -> Result<NativeLibrary, LibError> {
 /// let lib = load_lib(&path);
 ///
 /// assert!(lib.is_ok());
+/// ```
 #[cfg(target_os = "windows")]
 pub fn load_lib<P: AsRef<Path>>(path: P) -> Result<NativeLibrary, LibError> {
     use std::ffi::CString;
@@ -199,7 +234,10 @@ pub fn load_lib<P: AsRef<Path>>(path: P) -> Result<NativeLibrary, LibError> {
     let lib = unsafe { LoadLibraryA(win_path.as_ptr()) };
 
     if lib.is_null() {
-        return Err(LibError::FailedToLoadLib);
+        return Err(LibError::FailedToLoadLib {
+            path: path.to_path_buf(),
+            reason: last_error(),
+        });
     }
 
     let lib_name = path
@@ -231,6 +269,20 @@ impl<T> Clone for NativeMethod<T> {
     }
 }
 
+impl<T> NativeMethod<T> {
+    /// wraps a function pointer
+    ///
+    /// # Safety
+    ///
+    /// `T` has to be the `fn` type of the function
+    pub unsafe fn from_raw(inner: *mut c_void) -> NativeMethod<T> {
+        NativeMethod {
+            inner,
+            pd: PhantomData,
+        }
+    }
+}
+
 impl<T> Deref for NativeMethod<T> {
     type Target = T;
 
@@ -238,3 +290,218 @@ impl<T> Deref for NativeMethod<T> {
         unsafe { &*(&self.inner as *const *mut _ as *const T) }
     }
 }
+
+/// how an export was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportStatus {
+    /// found under its own name
+    Found,
+    /// missing under its own name, but found under the contained alias
+    Aliased(&'static str),
+    /// not found at all
+    Missing,
+}
+
+/// the resolution result of a single export
+#[derive(Debug, Clone)]
+pub struct ExportEntry {
+    /// the name of the export
+    pub name: &'static str,
+    /// whether the export table can't be used without it
+    pub required: bool,
+    /// how it was resolved
+    pub status: ExportStatus,
+}
+
+/// a per-symbol report of an export table lookup, see [`native_exports`](crate::native_exports)
+#[derive(Debug, Clone, Default)]
+pub struct ExportReport {
+    /// the name of the library the exports were looked up in
+    pub library: String,
+    /// every export of the table, in declaration order
+    pub entries: Vec<ExportEntry>,
+}
+
+impl ExportReport {
+    /// creates an empty report for a library
+    pub fn new(library: &str) -> ExportReport {
+        ExportReport {
+            library: library.to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// looks up an export, trying its aliases if it's missing, and records the result
+    pub fn resolve<T>(
+        &mut self,
+        lib: &NativeLibrary,
+        name: &'static str,
+        required: bool,
+        aliases: &[&'static str],
+    ) -> Option<NativeMethod<T>> {
+        let mut status = ExportStatus::Missing;
+        let mut method = lib.sym(name).ok();
+
+        if method.is_some() {
+            status = ExportStatus::Found;
+        } else if let Some((alias, alias_method)) = aliases
+            .iter()
+            .find_map(|alias| lib.sym(alias).ok().map(|method| (*alias, method)))
+        {
+            status = ExportStatus::Aliased(alias);
+            method = Some(alias_method);
+        }
+
+        self.entries.push(ExportEntry {
+            name,
+            required,
+            status,
+        });
+
+        method
+    }
+
+    /// exports found under their own name
+    pub fn found(&self) -> impl Iterator<Item = &ExportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == ExportStatus::Found)
+    }
+
+    /// exports found under an alias
+    pub fn aliased(&self) -> impl Iterator<Item = &ExportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.status, ExportStatus::Aliased(_)))
+    }
+
+    /// exports that weren't found at all
+    pub fn missing(&self) -> impl Iterator<Item = &ExportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == ExportStatus::Missing)
+    }
+
+    /// required exports that weren't found at all
+    pub fn missing_required(&self) -> impl Iterator<Item = &ExportEntry> {
+        self.missing().filter(|entry| entry.required)
+    }
+
+    /// whether every required export was found
+    pub fn is_supported(&self) -> bool {
+        self.missing_required().next().is_none()
+    }
+}
+
+impl Display for ExportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} found, {} aliased, {} missing",
+            self.library,
+            self.found().count(),
+            self.aliased().count(),
+            self.missing().count()
+        )?;
+
+        let missing_required: Vec<&str> = self.missing_required().map(|entry| entry.name).collect();
+
+        if !missing_required.is_empty() {
+            write!(f, " (required: {})", missing_required.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// declares a table of exports, resolved from a [`NativeLibrary`] in one pass
+///
+/// every export is marked `required` or `optional`, and may list aliases it's
+/// looked up under when the export itself is missing.
+/// this generates the struct, `resolve`, which returns an [`ExportReport`] alongside the table,
+/// and `new`, which fails with `LibError::MissingExports` if a required export is missing.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use unity_rs::{libs::{load_lib, ExportStatus}, native_exports};
+///
+/// native_exports! {
+///     #[derive(Debug, Clone)]
+///     pub struct LibcExports {
+///         required strlen: fn(*const c_char) -> usize,
+///         optional not_a_real_export: fn() | "abs",
+///         optional also_not_real: fn(),
+///     }
+/// }
+///
+/// # #[cfg(target_os = "linux")]
+/// # fn main() -> Result<(), unity_rs::libs::LibError> {
+/// let lib = load_lib("libc.so.6")?;
+/// let (exports, report) = LibcExports::resolve(&lib);
+///
+/// assert!(report.is_supported());
+/// assert_eq!(report.entries[1].status, ExportStatus::Aliased("abs"));
+/// assert!(exports.also_not_real.is_none());
+///
+/// println!("{}", report);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(target_os = "linux"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! native_exports {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $kind:ident $field:ident: $ty:ty $(| $alias:literal)*,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                pub $field: Option<$crate::libs::NativeMethod<$ty>>,
+            )*
+        }
+
+        impl $name {
+            /// looks up every export, reporting which were found, aliased or missing
+            pub fn resolve(lib: &$crate::libs::NativeLibrary) -> ($name, $crate::libs::ExportReport) {
+                let mut report = $crate::libs::ExportReport::new(&lib.name);
+
+                let exports = $name {
+                    $(
+                        $field: report.resolve(
+                            lib,
+                            stringify!($field),
+                            $crate::native_exports!(@required $kind),
+                            &[$($alias),*],
+                        ),
+                    )*
+                };
+
+                (exports, report)
+            }
+
+            /// looks up every export, failing if a required one is missing
+            pub fn new(lib: &$crate::libs::NativeLibrary) -> Result<$name, $crate::libs::LibError> {
+                let (exports, report) = $name::resolve(lib);
+
+                if !report.is_supported() {
+                    return Err($crate::libs::LibError::MissingExports(report));
+                }
+
+                Ok(exports)
+            }
+        }
+    };
+    (@required required) => { true };
+    (@required optional) => { false };
+}
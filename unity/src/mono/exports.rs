@@ -1,101 +1,111 @@
 //! TODO
 
+#![allow(clippy::type_complexity)]
+
 use std::ffi::{c_char, c_int, c_void};
 
-use crate::libs::{LibError, NativeLibrary, NativeMethod};
+use crate::native_exports;
 
 use super::types::{
-    MonoAssembly, MonoClass, MonoDomain, MonoImage, MonoMethod, MonoObject, MonoString, MonoThread,
+    AssemblyName, MonoArray, MonoAssembly, MonoClass, MonoClassField, MonoDomain, MonoException, MonoImage, MonoMethod,
+    MonoMethodSignature, MonoObject, MonoProperty, MonoString, MonoThread, MonoType, MonoVTable,
 };
 
-#[derive(Debug, Clone)]
-pub struct MonoExports {
-    pub mono_jit_init_version:
-        Option<NativeMethod<fn(*const c_char, *const c_char) -> *mut MonoDomain>>,
-    pub mono_debug_domain_create: Option<NativeMethod<fn(*mut MonoDomain)>>,
-    pub mono_thread_current: Option<NativeMethod<fn() -> *mut MonoThread>>,
-    pub mono_thread_set_main: Option<NativeMethod<fn(*mut MonoThread)>>,
-    pub mono_thread_attach: Option<NativeMethod<fn(*mut MonoDomain) -> *mut MonoThread>>,
-    pub mono_domain_set_config:
-        Option<NativeMethod<fn(*mut MonoDomain, *const c_char, *const c_char)>>,
-    pub mono_add_internal_call: Option<NativeMethod<fn(*const c_char, *mut c_void)>>,
-    pub mono_get_root_domain: Option<NativeMethod<fn() -> *mut MonoDomain>>,
-    pub mono_string_new: Option<NativeMethod<fn(*mut MonoDomain, *const c_char) -> *mut MonoString>>,
-    pub mono_domain_assembly_open: Option<NativeMethod<fn(*mut MonoDomain, *const c_char) -> *mut MonoAssembly>>,
-    pub mono_assembly_get_image: Option<NativeMethod<fn(*mut MonoAssembly) -> *mut MonoImage>>,
-    pub mono_class_from_name:
-        Option<NativeMethod<fn(*mut MonoImage, *const c_char, *const c_char) -> *mut MonoClass>>,
-    pub mono_class_get_method_from_name:
-        Option<NativeMethod<fn(*mut MonoClass, *const c_char, c_int) -> *mut MonoMethod>>,
-    pub mono_runtime_invoke: Option<
-        NativeMethod<
-            fn(
-                *mut MonoMethod,
-                *mut MonoObject,
-                *mut *mut c_void,
-                *mut *mut MonoObject,
-            ) -> *mut MonoObject,
-        >,
-    >,
-    pub mono_object_to_string:
-        Option<NativeMethod<fn(*mut MonoObject, *mut *mut MonoObject) -> *mut MonoString>>,
-    pub mono_string_to_utf8: Option<NativeMethod<fn(*mut MonoString) -> *const c_char>>,
-    pub mono_method_get_name: Option<NativeMethod<fn(*mut MonoMethod) -> *const c_char>>,
-    pub mono_install_assembly_preload_hook: Option<NativeMethod<fn(*mut c_void, *mut c_void)>>,
-    pub mono_install_assembly_search_hook: Option<NativeMethod<fn(*mut c_void, *mut c_void)>>,
-    pub mono_install_assembly_load_hook: Option<NativeMethod<fn(*mut c_void, *mut c_void)>>,
-    pub mono_assembly_get_object: Option<NativeMethod<fn(*mut MonoDomain, *mut MonoAssembly) -> *mut MonoObject>>,
-}
-
-impl MonoExports {
-    pub fn new(lib: &NativeLibrary) -> Result<Self, LibError> {
-        Ok(MonoExports {
-            mono_jit_init_version: Some(lib.sym("mono_jit_init_version")?),
-            mono_debug_domain_create: {
-                // probably not present on old mono
-                let res = lib.sym("mono_debug_domain_create");
-                match res.is_err() {
-                    true => None,
-                    false => Some(res.unwrap()),
-                }
-            },
-            mono_string_new: Some(lib.sym("mono_string_new")?),
-            mono_runtime_invoke: Some(lib.sym("mono_runtime_invoke")?),
-            mono_string_to_utf8: Some(lib.sym("mono_string_to_utf8")?),
-            mono_thread_current: Some(lib.sym("mono_thread_current")?),
-            mono_thread_attach: Some(lib.sym("mono_thread_attach")?),
-            mono_get_root_domain: Some(lib.sym("mono_get_root_domain")?),
-            mono_class_from_name: Some(lib.sym("mono_class_from_name")?),
-            mono_method_get_name: Some(lib.sym("mono_method_get_name")?),
-            mono_thread_set_main: Some(lib.sym("mono_thread_set_main")?),
-            mono_object_to_string: {
-                // probably not present on old mono
-                let res = lib.sym("mono_object_to_string");
-
-                match res.is_err() {
-                    true => None,
-                    false => Some(res.unwrap()),
-                }
-            },
-            mono_add_internal_call: Some(lib.sym("mono_add_internal_call")?),
-            mono_domain_set_config: {
-                // probably not present on old mono
-                let res = lib.sym("mono_domain_set_config");
-
-                match res.is_err() {
-                    true => None,
-                    false => Some(res.unwrap()),
-                }
-            },
-            mono_assembly_get_image: Some(lib.sym("mono_assembly_get_image")?),
-            mono_assembly_get_object: Some(lib.sym("mono_assembly_get_object")?),
-            mono_domain_assembly_open: Some(lib.sym("mono_domain_assembly_open")?),
-            mono_install_assembly_load_hook: Some(lib.sym("mono_install_assembly_load_hook")?),
-            mono_class_get_method_from_name: Some(lib.sym("mono_class_get_method_from_name")?),
-            mono_install_assembly_search_hook: Some(lib.sym("mono_install_assembly_search_hook")?),
-            mono_install_assembly_preload_hook: Some(
-                lib.sym("mono_install_assembly_preload_hook")?,
-            ),
-        })
+native_exports! {
+    /// Various methods exported by mono
+    ///
+    /// only the exports the core api needs are `required`. `optional` exports are `None` when missing,
+    /// either because old mono lacks them or because only a single feature uses them, which then fails
+    /// with `RuntimeError::MissingFunction`.
+    #[derive(Debug, Clone)]
+    pub struct MonoExports {
+        required mono_jit_init_version: fn(*const c_char, *const c_char) -> *mut MonoDomain,
+        optional mono_debug_domain_create: fn(*mut MonoDomain),
+        required mono_thread_current: fn() -> *mut MonoThread,
+        optional mono_thread_set_main: fn(*mut MonoThread),
+        optional mono_thread_get_main: fn() -> *mut MonoThread,
+        required mono_thread_attach: fn(*mut MonoDomain) -> *mut MonoThread,
+        optional mono_thread_detach: fn(*mut MonoThread),
+        optional mono_domain_set_config: fn(*mut MonoDomain, *const c_char, *const c_char),
+        required mono_add_internal_call: fn(*const c_char, *mut c_void),
+        required mono_get_root_domain: fn() -> *mut MonoDomain,
+        optional mono_domain_get: fn() -> *mut MonoDomain,
+        required mono_string_new: fn(*mut MonoDomain, *const c_char) -> *mut MonoString,
+        required mono_domain_assembly_open: fn(*mut MonoDomain, *const c_char) -> *mut MonoAssembly,
+        required mono_assembly_get_image: fn(*mut MonoAssembly) -> *mut MonoImage,
+        optional mono_assembly_get_name: fn(*mut MonoAssembly) -> *mut AssemblyName,
+        optional mono_assembly_foreach: fn(extern "C" fn(*mut MonoAssembly, *mut c_void), *mut c_void),
+        required mono_image_get_name: fn(*mut MonoImage) -> *const c_char,
+        optional mono_image_get_filename: fn(*mut MonoImage) -> *const c_char,
+        required mono_class_from_name: fn(*mut MonoImage, *const c_char, *const c_char) -> *mut MonoClass,
+        required mono_class_get_name: fn(*mut MonoClass) -> *const c_char,
+        required mono_class_get_namespace: fn(*mut MonoClass) -> *const c_char,
+        optional mono_class_get_methods: fn(*mut MonoClass, *mut *mut c_void) -> *mut MonoMethod,
+        optional mono_class_get_fields: fn(*mut MonoClass, *mut *mut c_void) -> *mut MonoClassField,
+        optional mono_class_get_properties: fn(*mut MonoClass, *mut *mut c_void) -> *mut MonoProperty,
+        optional mono_class_get_nested_types: fn(*mut MonoClass, *mut *mut c_void) -> *mut MonoClass,
+        optional mono_class_get_interfaces: fn(*mut MonoClass, *mut *mut c_void) -> *mut MonoClass,
+        optional mono_class_get_parent: fn(*mut MonoClass) -> *mut MonoClass,
+        optional mono_class_get_flags: fn(*mut MonoClass) -> u32,
+        required mono_class_get_field_from_name: fn(*mut MonoClass, *const c_char) -> *mut MonoClassField,
+        required mono_class_vtable: fn(*mut MonoDomain, *mut MonoClass) -> *mut MonoVTable,
+        required mono_class_from_mono_type: fn(*mut MonoType) -> *mut MonoClass,
+        optional mono_class_get_type: fn(*mut MonoClass) -> *mut MonoType,
+        required mono_field_get_name: fn(*mut MonoClassField) -> *const c_char,
+        required mono_field_get_flags: fn(*mut MonoClassField) -> u32,
+        required mono_field_get_offset: fn(*mut MonoClassField) -> u32,
+        required mono_field_get_type: fn(*mut MonoClassField) -> *mut MonoType,
+        optional mono_field_get_parent: fn(*mut MonoClassField) -> *mut MonoClass,
+        required mono_field_get_value: fn(*mut MonoObject, *mut MonoClassField, *mut c_void),
+        required mono_field_set_value: fn(*mut MonoObject, *mut MonoClassField, *mut c_void),
+        required mono_field_static_get_value: fn(*mut MonoVTable, *mut MonoClassField, *mut c_void),
+        required mono_field_static_set_value: fn(*mut MonoVTable, *mut MonoClassField, *mut c_void),
+        required mono_class_get_property_from_name: fn(*mut MonoClass, *const c_char) -> *mut MonoProperty,
+        required mono_property_get_name: fn(*mut MonoProperty) -> *const c_char,
+        required mono_property_get_get_method: fn(*mut MonoProperty) -> *mut MonoMethod,
+        required mono_property_get_set_method: fn(*mut MonoProperty) -> *mut MonoMethod,
+        required mono_type_get_name: fn(*mut MonoType) -> *mut c_char,
+        optional mono_type_get_type: fn(*mut MonoType) -> c_int,
+        optional mono_type_size: fn(*mut MonoType, *mut c_int) -> c_int,
+        required mono_class_get_method_from_name: fn(*mut MonoClass, *const c_char, c_int) -> *mut MonoMethod,
+        required mono_compile_method: fn(*mut MonoMethod) -> *mut c_void,
+        required mono_runtime_invoke: fn(*mut MonoMethod, *mut MonoObject, *mut *mut c_void, *mut *mut MonoObject) -> *mut MonoObject,
+        optional mono_object_to_string: fn(*mut MonoObject, *mut *mut MonoObject) -> *mut MonoString,
+        optional mono_array_new: fn(*mut MonoDomain, *mut MonoClass, usize) -> *mut MonoArray,
+        optional mono_array_length: fn(*mut MonoArray) -> usize,
+        optional mono_array_element_size: fn(*mut MonoClass) -> i32,
+        optional mono_gc_wbarrier_set_arrayref: fn(*mut MonoArray, *mut c_void, *mut MonoObject),
+        optional mono_gchandle_new: fn(*mut MonoObject, i32) -> u32,
+        optional mono_gchandle_new_weakref: fn(*mut MonoObject, i32) -> u32,
+        optional mono_gchandle_get_target: fn(u32) -> *mut MonoObject,
+        optional mono_gchandle_free: fn(u32),
+        required mono_get_corlib: fn() -> *mut MonoImage,
+        optional mono_exception_from_name_msg: fn(*mut MonoImage, *const c_char, *const c_char, *const c_char) -> *mut MonoException,
+        optional mono_raise_exception: fn(*mut MonoException),
+        required mono_object_get_class: fn(*mut MonoObject) -> *mut MonoClass,
+        optional mono_object_new: fn(*mut MonoDomain, *mut MonoClass) -> *mut MonoObject,
+        optional mono_object_isinst: fn(*mut MonoObject, *mut MonoClass) -> *mut MonoObject,
+        optional mono_object_get_virtual_method: fn(*mut MonoObject, *mut MonoMethod) -> *mut MonoMethod,
+        optional mono_value_box: fn(*mut MonoDomain, *mut MonoClass, *mut c_void) -> *mut MonoObject,
+        optional mono_object_unbox: fn(*mut MonoObject) -> *mut c_void,
+        optional mono_class_is_valuetype: fn(*mut MonoClass) -> i32,
+        optional mono_class_value_size: fn(*mut MonoClass, *mut u32) -> i32,
+        required mono_string_to_utf8: fn(*mut MonoString) -> *const c_char,
+        optional mono_string_new_utf16: fn(*mut MonoDomain, *const u16, i32) -> *mut MonoString,
+        optional mono_string_chars: fn(*mut MonoString) -> *mut u16,
+        optional mono_string_length: fn(*mut MonoString) -> c_int,
+        optional mono_free: fn(*mut c_void) | "g_free",
+        required mono_method_get_name: fn(*mut MonoMethod) -> *const c_char,
+        required mono_method_get_class: fn(*mut MonoMethod) -> *mut MonoClass,
+        optional mono_method_get_flags: fn(*mut MonoMethod, *mut u32) -> u32,
+        required mono_method_signature: fn(*mut MonoMethod) -> *mut MonoMethodSignature,
+        required mono_signature_get_param_count: fn(*mut MonoMethodSignature) -> u32,
+        optional mono_signature_get_return_type: fn(*mut MonoMethodSignature) -> *mut MonoType,
+        optional mono_signature_get_params: fn(*mut MonoMethodSignature, *mut *mut c_void) -> *mut MonoType,
+        optional mono_method_get_param_names: fn(*mut MonoMethod, *mut *const c_char),
+        optional mono_install_assembly_preload_hook: fn(*mut c_void, *mut c_void),
+        optional mono_install_assembly_search_hook: fn(*mut c_void, *mut c_void),
+        optional mono_install_assembly_load_hook: fn(*mut c_void, *mut c_void),
+        optional mono_assembly_get_object: fn(*mut MonoDomain, *mut MonoAssembly) -> *mut MonoObject,
     }
 }
@@ -1,36 +1,53 @@
 //! TODO
 
-use std::{error, path::PathBuf, ptr::addr_of, fmt::{Display, self}, ffi::CString};
-
-use thiserror::Error;
+use std::{convert::Infallible, path::PathBuf, fmt::{Display, self}, ffi::{CString, CStr, c_void, c_char}, slice, ptr, sync::{Arc, atomic::{AtomicPtr, Ordering}}};
 
 use crate::{
-    common::{thread::UnityThread, domain::UnityDomain, method::{MethodPointer}},
-    libs::{self, NativeLibrary, NativeMethod}, runtime::{Runtime, RuntimeError, RuntimeType},
+    common::{gc_handle::GcHandleKind, thread::UnityThread, domain::UnityDomain, callback, init::{self, InitCallback}, internal_call, method::{MethodPointer}, string::UnityString, class::UnityClass, exception::ManagedException, method::UnityMethod, object::{self, UnityObject}, assembly::UnityAssembly, image::UnityImage, field::UnityField, property::UnityProperty, ty::UnityType},
+    libs::{self, NativeLibrary, NativeMethod, ExportReport, LibError}, runtime::{Runtime, RuntimeError, RuntimeType, LookupKind},
+    utils::{ffi::c_str_to_string, path::PathError},
 };
 
-use self::{exports::MonoExports, types::{MonoThread, MonoDomain}};
+use self::{exports::MonoExports, types::{MonoObject, MonoMethodSignature, MonoAssembly, MonoDomain, MonoVTable, AssemblyName}};
 
 pub mod exports;
 pub mod types;
 
+/// `MONO_TYPE_GENERICINST`, the type of an instantiated generic like `List<int>`
+const MONO_TYPE_GENERICINST: i32 = 0x15;
+
+/// resolves an assembly by name, returning `None` to let the runtime carry on
+pub type AssemblyResolver = Box<dyn Fn(&AssemblyName) -> Option<UnityAssembly> + Send + Sync>;
+
+/// notified of a loaded assembly
+pub type AssemblyLoadCallback = Box<dyn Fn(&UnityAssembly) + Send + Sync>;
+
 /// assembly hook types
-#[derive(Debug, Clone, Copy)]
 pub enum AssemblyHookType {
-    /// called when an assembly is loaded
-    Preload,
-    /// called when an assembly is unloaded
-    Load,
-    /// called when an assembly is searched
-    Search,
+    /// called before an assembly is loaded, may return the assembly to use instead
+    Preload(AssemblyResolver),
+    /// called after an assembly is loaded
+    Load(AssemblyLoadCallback),
+    /// called when an assembly is searched, may return the assembly that was asked for
+    Search(AssemblyResolver),
 }
 
 impl Display for AssemblyHookType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            AssemblyHookType::Preload => write!(f, "preload"),
-            AssemblyHookType::Load => write!(f, "load"),
-            AssemblyHookType::Search => write!(f, "search"),
+            AssemblyHookType::Preload(_) => write!(f, "preload"),
+            AssemblyHookType::Load(_) => write!(f, "load"),
+            AssemblyHookType::Search(_) => write!(f, "search"),
+        }
+    }
+}
+
+impl fmt::Debug for AssemblyHookType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssemblyHookType::Preload(_) => write!(f, "Preload(..)"),
+            AssemblyHookType::Load(_) => write!(f, "Load(..)"),
+            AssemblyHookType::Search(_) => write!(f, "Search(..)"),
         }
     }
 }
@@ -40,41 +57,152 @@ pub struct Mono {
     pub is_old: bool,
     pub mono_lib: NativeLibrary,
     pub exports: MonoExports,
+    pub export_report: ExportReport,
+    /// the thread passed to `set_main_thread`
+    main_thread: Arc<AtomicPtr<c_void>>,
 }
 
 impl Mono {
-    pub fn new(mono_path: PathBuf) -> Result<Self, Box<dyn error::Error>> {
+    pub fn new(mono_path: PathBuf) -> Result<Self, RuntimeError> {
         if !mono_path.exists() {
-            return Err(Box::new(RuntimeError::MonoLibPath));
+            return Err(PathError::MonoNotFound.into());
         }
 
         let lib_name = mono_path
             .file_stem()
-            .ok_or(RuntimeError::MonoLibName)?
-            .to_str()
-            .ok_or(RuntimeError::MonoLibName)?;
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| PathError::InvalidFileName(mono_path.clone()))?;
 
         let is_old = lib_name == "mono" || lib_name == "libmono";
 
         let mono_lib = libs::load_lib(&mono_path)?;
 
-        let exports = MonoExports::new(&mono_lib)?;
+        let (exports, export_report) = MonoExports::resolve(&mono_lib);
+
+        if !export_report.is_supported() {
+            return Err(LibError::MissingExports(export_report).into());
+        }
 
         let mono = Mono {
             is_old,
             mono_lib,
             exports,
+            export_report,
+            main_thread: Arc::default(),
         };
 
         Ok(mono)
     }
+
+    /// frees memory handed out by mono, such as the result of `mono_string_to_utf8`
+    pub fn free(&self, ptr: *mut c_void) {
+        if ptr.is_null() {
+            return;
+        }
+
+        // old mono only has g_free, if neither exists leaking is safer than freeing with the wrong allocator
+        if let Some(function) = &self.exports.mono_free {
+            function(ptr);
+        }
+    }
+
+    /// static fields live in the vtable of their class, which is per domain
+    fn get_field_vtable(&self, field: &UnityField) -> Result<*mut MonoVTable, RuntimeError> {
+        let parent_function = self.exports.mono_field_get_parent.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_get_parent"))?;
+        let vtable_function = self.exports.mono_class_vtable.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_vtable"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        let domain = self.get_domain()?;
+        let class = parent_function(field.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_field_get_parent"));
+        }
+
+        let vtable = vtable_function(domain.inner.cast(), class);
+
+        if vtable.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_class_vtable"));
+        }
+
+        Ok(vtable)
+    }
+
+    /// the signature holds the parameter and return types of a method
+    fn get_method_signature(&self, method: &UnityMethod) -> Result<*mut MonoMethodSignature, RuntimeError> {
+        let function = self.exports.mono_method_signature.as_ref().ok_or(RuntimeError::MissingFunction("mono_method_signature"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let signature = function(method.inner.cast());
+
+        if signature.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_method_signature"));
+        }
+
+        Ok(signature)
+    }
+
+    /// reads a thrown exception, through `ToString` where available and its properties otherwise
+    fn read_exception(&self, exception: *mut MonoObject) -> ManagedException {
+        let class = self.exports
+            .mono_object_get_class
+            .as_ref()
+            .map(|function| UnityClass { inner: function(exception).cast() });
+        let class_name = ManagedException::class_name(self, class);
+
+        if let Some(function) = &self.exports.mono_object_to_string {
+            let mut inner_exception = ptr::null_mut();
+            let string = function(exception, &mut inner_exception);
+
+            if !string.is_null() && inner_exception.is_null() {
+                if let Ok(text) = self.string_to_utf8(&UnityString { inner: string.cast() }) {
+                    return ManagedException::from_to_string(class_name, &text);
+                }
+            }
+        }
+
+        ManagedException {
+            class: class_name,
+            message: self.read_exception_property(exception, "Message").unwrap_or_default(),
+            stack_trace: self.read_exception_property(exception, "StackTrace").unwrap_or_default(),
+        }
+    }
+
+    /// invokes a string getter on an exception, ignoring anything it throws itself
+    fn read_exception_property(&self, exception: *mut MonoObject, name: &str) -> Option<String> {
+        let object_get_class = self.exports.mono_object_get_class.as_ref()?;
+        let invoke = self.exports.mono_runtime_invoke.as_ref()?;
+
+        let class = UnityClass { inner: object_get_class(exception).cast() };
+        let property = self.get_property(&class, name).ok()?;
+        let getter = self.get_property_get_method(&property).ok()??;
+
+        let mut inner_exception = ptr::null_mut();
+        let result = invoke(getter.inner.cast(), exception, ptr::null_mut(), &mut inner_exception);
+
+        if result.is_null() || !inner_exception.is_null() {
+            return None;
+        }
+
+        self.string_to_utf8(&UnityString { inner: result.cast() }).ok()
+    }
 }
 
 impl Runtime for Mono {
-    fn get_type(&self) -> RuntimeType {
+    fn get_type(&self) -> RuntimeType<'_> {
         RuntimeType::Mono(self)
     }
 
+    fn get_export_report(&self) -> &ExportReport {
+        &self.export_report
+    }
+
     fn get_export_ptr(&self, name: &str) -> Result<MethodPointer, RuntimeError> {
         let function: NativeMethod<fn()> = self.mono_lib.sym(name)?;
 
@@ -85,12 +213,29 @@ impl Runtime for Mono {
         Ok(function.inner)
     }
 
+    fn on_initialized(&self, callback: InitCallback) -> Result<(), RuntimeError> {
+        // the root domain only exists once the jit is initialized
+        init::register(callback, || self.get_domain().ok(), || {
+            let function = self.exports.mono_jit_init_version.as_ref().ok_or(RuntimeError::MissingFunction("mono_jit_init_version"))?;
+
+            unsafe { function.hook(jit_init_version_detour as MethodPointer) }
+        })
+    }
+
     fn get_current_thread(&self) -> Result<UnityThread, RuntimeError> {
-        let function = &self.exports.clone().mono_thread_current.ok_or(RuntimeError::MissingFunction("mono_thread_current"))?;
+        let function = self.exports.mono_thread_current.as_ref().ok_or(RuntimeError::MissingFunction("mono_thread_current"))?;
+
+        // newer mono asserts in mono_thread_current on threads it doesn't know, which have no domain
+        if let Some(domain_function) = self.exports.mono_domain_get.as_ref() {
+            if domain_function().is_null() {
+                return Err(RuntimeError::ThreadNotAttached);
+            }
+        }
+
         let thread = function();
 
         if thread.is_null() {
-            return Err(RuntimeError::ReturnedNull("mono_thread_current").into());
+            return Err(RuntimeError::ReturnedNull("mono_thread_current"));
         }
 
         Ok(UnityThread {
@@ -99,41 +244,70 @@ impl Runtime for Mono {
     }
 
     fn set_main_thread(&self, thread: UnityThread) -> Result<(), RuntimeError> {
-        let function = &self.exports.clone().mono_thread_set_main.ok_or(RuntimeError::MissingFunction("mono_thread_set_main"))?;
+        let function = self.exports.mono_thread_set_main.as_ref().ok_or(RuntimeError::MissingFunction("mono_thread_set_main"))?;
 
         if thread.inner.is_null() {
-            return Err(RuntimeError::ReturnedNull("mono_thread_set_main"));
+            return Err(RuntimeError::NullPointer("thread"));
         }
 
         function(thread.inner.cast());
+        self.main_thread.store(thread.inner, Ordering::Release);
+
         Ok(())
     }
 
-    fn attach_to_thread(&self, thread: UnityDomain) -> Result<UnityThread, RuntimeError> {
-        let function = &self.exports.clone().mono_thread_attach.ok_or(RuntimeError::MissingFunction("mono_thread_attach"))?;
+    fn main_thread(&self) -> Result<UnityThread, RuntimeError> {
+        let mut thread = self.main_thread.load(Ordering::Acquire);
 
-        if thread.inner.is_null() {
-            return Err(RuntimeError::ReturnedNull("mono_thread_attach"));
+        // mono knows its main thread, even if it was set by unity instead of us
+        if let (true, Some(function)) = (thread.is_null(), self.exports.mono_thread_get_main.as_ref()) {
+            thread = function().cast();
+        }
+
+        if thread.is_null() {
+            return Err(RuntimeError::MainThreadNotSet);
+        }
+
+        Ok(UnityThread {
+            inner: thread,
+        })
+    }
+
+    fn attach_to_thread(&self, domain: UnityDomain) -> Result<UnityThread, RuntimeError> {
+        let function = self.exports.mono_thread_attach.as_ref().ok_or(RuntimeError::MissingFunction("mono_thread_attach"))?;
+
+        if domain.inner.is_null() {
+            return Err(RuntimeError::NullPointer("domain"));
         }
 
-        let result = function(thread.inner.cast());
+        let thread = function(domain.inner.cast());
 
-        if result.is_null() {
+        if thread.is_null() {
             return Err(RuntimeError::ReturnedNull("mono_thread_attach"));
         }
 
         Ok(UnityThread {
-            inner: result.cast(),
+            inner: thread.cast(),
         })
     }
 
-    fn add_internal_call(&self, name: String, func: MethodPointer) -> Result<(), RuntimeError> {
-        let function = &self.exports.clone().mono_add_internal_call.ok_or(RuntimeError::MissingFunction("mono_add_internal_call"))?;
+    fn detach_from_thread(&self, thread: UnityThread) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_thread_detach.as_ref().ok_or(RuntimeError::MissingFunction("mono_thread_detach"))?;
 
-        if name.is_empty() {
-            return Err(RuntimeError::EmptyString);
+        if thread.inner.is_null() {
+            return Err(RuntimeError::NullPointer("thread"));
         }
 
+        function(thread.inner.cast());
+
+        Ok(())
+    }
+
+    fn add_internal_call(&self, name: String, func: MethodPointer) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_add_internal_call.as_ref().ok_or(RuntimeError::MissingFunction("mono_add_internal_call"))?;
+
+        internal_call::validate_name(&name)?;
+
         if func.is_null() {
             return Err(RuntimeError::NullPointer("func"));
         }
@@ -145,24 +319,28 @@ impl Runtime for Mono {
         Ok(())
     }
 
-    fn install_assembly_hook(&self, hook_type: AssemblyHookType, func: MethodPointer) -> Result<(), RuntimeError> {
-        if func.is_null() {
-            return Err(RuntimeError::NullPointer("func"));
+    fn install_assembly_hook(&self, hook_type: AssemblyHookType) -> Result<(), RuntimeError> {
+        // the closures are handed to mono as user data, and stay alive for as long as the hooks do, which is forever
+        match hook_type {
+            AssemblyHookType::Preload(resolver) => {
+                let function = self.exports.mono_install_assembly_preload_hook.as_ref().ok_or(RuntimeError::MissingFunction("mono_install_assembly_preload_hook"))?;
+                function(preload_trampoline as *mut c_void, Box::into_raw(Box::new(resolver)).cast());
+            }
+            AssemblyHookType::Load(callback) => {
+                let function = self.exports.mono_install_assembly_load_hook.as_ref().ok_or(RuntimeError::MissingFunction("mono_install_assembly_load_hook"))?;
+                function(load_trampoline as *mut c_void, Box::into_raw(Box::new(callback)).cast());
+            }
+            AssemblyHookType::Search(resolver) => {
+                let function = self.exports.mono_install_assembly_search_hook.as_ref().ok_or(RuntimeError::MissingFunction("mono_install_assembly_search_hook"))?;
+                function(search_trampoline as *mut c_void, Box::into_raw(Box::new(resolver)).cast());
+            }
         }
 
-        let hook_func = match hook_type {
-            AssemblyHookType::Preload => self.exports.clone().mono_install_assembly_preload_hook,
-            AssemblyHookType::Load => self.exports.clone().mono_install_assembly_load_hook,
-            AssemblyHookType::Search => self.exports.clone().mono_install_assembly_search_hook,
-        }.ok_or(RuntimeError::MissingFunction("mono_install_assembly_hook"))?;
-
-        hook_func(func, std::ptr::null_mut());
-
         Ok(())
     }
 
     fn get_domain(&self) -> Result<UnityDomain, RuntimeError> {
-        let function = &self.exports.clone().mono_get_root_domain.ok_or(RuntimeError::MissingFunction("mono_get_root_domain"))?;
+        let function = self.exports.mono_get_root_domain.as_ref().ok_or(RuntimeError::MissingFunction("mono_get_root_domain"))?;
 
         let domain = function();
 
@@ -174,4 +352,1106 @@ impl Runtime for Mono {
             inner: domain.cast(),
         })
     }
+
+    fn new_string(&self, value: &str) -> Result<UnityString, RuntimeError> {
+        let function = self.exports.mono_string_new.as_ref().ok_or(RuntimeError::MissingFunction("mono_string_new"))?;
+
+        let domain = self.get_domain()?;
+        let value = CString::new(value)?;
+
+        let string = function(domain.inner.cast(), value.as_ptr());
+
+        if string.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_string_new"));
+        }
+
+        Ok(UnityString {
+            inner: string.cast(),
+        })
+    }
+
+    fn new_string_utf16(&self, value: &[u16]) -> Result<UnityString, RuntimeError> {
+        let function = self.exports.mono_string_new_utf16.as_ref().ok_or(RuntimeError::MissingFunction("mono_string_new_utf16"))?;
+
+        let domain = self.get_domain()?;
+        let length = i32::try_from(value.len()).map_err(|_| RuntimeError::InvalidString("too long"))?;
+
+        let string = function(domain.inner.cast(), value.as_ptr(), length);
+
+        if string.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_string_new_utf16"));
+        }
+
+        Ok(UnityString {
+            inner: string.cast(),
+        })
+    }
+
+    fn string_to_utf8(&self, string: &UnityString) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_string_to_utf8.as_ref().ok_or(RuntimeError::MissingFunction("mono_string_to_utf8"))?;
+
+        if string.inner.is_null() {
+            return Err(RuntimeError::NullPointer("string"));
+        }
+
+        let chars = function(string.inner.cast());
+
+        if chars.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_string_to_utf8"));
+        }
+
+        let result = unsafe { CStr::from_ptr(chars) }
+            .to_str()
+            .map(str::to_owned)
+            .map_err(|_| RuntimeError::InvalidString("not valid UTF-8"));
+
+        self.free(chars as *mut c_void);
+
+        result
+    }
+
+    fn string_to_utf16(&self, string: &UnityString) -> Result<Vec<u16>, RuntimeError> {
+        let chars_function = self.exports.mono_string_chars.as_ref().ok_or(RuntimeError::MissingFunction("mono_string_chars"))?;
+        let length_function = self.exports.mono_string_length.as_ref().ok_or(RuntimeError::MissingFunction("mono_string_length"))?;
+
+        if string.inner.is_null() {
+            return Err(RuntimeError::NullPointer("string"));
+        }
+
+        let chars = chars_function(string.inner.cast());
+        let length = length_function(string.inner.cast());
+
+        if chars.is_null() || length < 0 {
+            return Err(RuntimeError::ReturnedNull("mono_string_chars"));
+        }
+
+        Ok(unsafe { slice::from_raw_parts(chars, length as usize) }.to_vec())
+    }
+
+    fn get_assemblies(&self, domain: &UnityDomain) -> Result<Vec<UnityAssembly>, RuntimeError> {
+        let function = self.exports.mono_assembly_foreach.as_ref().ok_or(RuntimeError::MissingFunction("mono_assembly_foreach"))?;
+
+        if domain.inner.is_null() {
+            return Err(RuntimeError::NullPointer("domain"));
+        }
+
+        extern "C" fn collect(assembly: *mut MonoAssembly, user_data: *mut c_void) {
+            let assemblies = unsafe { &mut *user_data.cast::<Vec<UnityAssembly>>() };
+            assemblies.push(UnityAssembly {
+                inner: assembly.cast(),
+            });
+        }
+
+        // mono keeps a single process wide assembly list, unity only ever uses the root domain anyway
+        let mut assemblies: Vec<UnityAssembly> = Vec::new();
+        function(collect, (&mut assemblies as *mut Vec<UnityAssembly>).cast());
+
+        Ok(assemblies)
+    }
+
+    fn open_assembly(&self, domain: &UnityDomain, path: &str) -> Result<UnityAssembly, RuntimeError> {
+        let function = self.exports.mono_domain_assembly_open.as_ref().ok_or(RuntimeError::MissingFunction("mono_domain_assembly_open"))?;
+
+        if domain.inner.is_null() {
+            return Err(RuntimeError::NullPointer("domain"));
+        }
+
+        let assembly_path = CString::new(path)?;
+        let assembly = function(domain.inner.cast(), assembly_path.as_ptr());
+
+        if assembly.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Assembly, path));
+        }
+
+        Ok(UnityAssembly {
+            inner: assembly.cast(),
+        })
+    }
+
+    fn get_assembly_image(&self, assembly: &UnityAssembly) -> Result<UnityImage, RuntimeError> {
+        let function = self.exports.mono_assembly_get_image.as_ref().ok_or(RuntimeError::MissingFunction("mono_assembly_get_image"))?;
+
+        if assembly.inner.is_null() {
+            return Err(RuntimeError::NullPointer("assembly"));
+        }
+
+        let image = function(assembly.inner.cast());
+
+        if image.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_assembly_get_image"));
+        }
+
+        Ok(UnityImage {
+            inner: image.cast(),
+        })
+    }
+
+    fn get_assembly_name(&self, assembly: &UnityAssembly) -> Result<AssemblyName, RuntimeError> {
+        let function = self.exports.mono_assembly_get_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_assembly_get_name"))?;
+
+        if assembly.inner.is_null() {
+            return Err(RuntimeError::NullPointer("assembly"));
+        }
+
+        let name = function(assembly.inner.cast());
+
+        if name.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_assembly_get_name"));
+        }
+
+        Ok(unsafe { *name })
+    }
+
+    fn get_image_name(&self, image: &UnityImage) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_image_get_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_image_get_name"))?;
+
+        if image.inner.is_null() {
+            return Err(RuntimeError::NullPointer("image"));
+        }
+
+        c_str_to_string(function(image.inner.cast()), "mono_image_get_name")
+    }
+
+    fn get_image_filename(&self, image: &UnityImage) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_image_get_filename.as_ref().ok_or(RuntimeError::MissingFunction("mono_image_get_filename"))?;
+
+        if image.inner.is_null() {
+            return Err(RuntimeError::NullPointer("image"));
+        }
+
+        c_str_to_string(function(image.inner.cast()), "mono_image_get_filename")
+    }
+
+    fn get_class_from_image(&self, image: &UnityImage, namespace: &str, name: &str) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.mono_class_from_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_from_name"))?;
+
+        if image.inner.is_null() {
+            return Err(RuntimeError::NullPointer("image"));
+        }
+
+        let namespace_name = CString::new(namespace)?;
+        let class_name = CString::new(name)?;
+
+        let class = function(image.inner.cast(), namespace_name.as_ptr(), class_name.as_ptr());
+
+        if class.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Class, format!("{}.{}", namespace, name)));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    fn get_class_name(&self, class: &UnityClass) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_class_get_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        c_str_to_string(function(class.inner.cast()), "mono_class_get_name")
+    }
+
+    fn get_class_namespace(&self, class: &UnityClass) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_class_get_namespace.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_namespace"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        c_str_to_string(function(class.inner.cast()), "mono_class_get_namespace")
+    }
+
+    fn next_class_method(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityMethod>, RuntimeError> {
+        let function = self.exports.mono_class_get_methods.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_methods"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityMethod {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_class_field(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityField>, RuntimeError> {
+        let function = self.exports.mono_class_get_fields.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_fields"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityField {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_class_property(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityProperty>, RuntimeError> {
+        let function = self.exports.mono_class_get_properties.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_properties"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityProperty {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_nested_type(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityClass>, RuntimeError> {
+        let function = self.exports.mono_class_get_nested_types.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_nested_types"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityClass {
+            inner: member.cast(),
+        }))
+    }
+
+    fn next_class_interface(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityClass>, RuntimeError> {
+        let function = self.exports.mono_class_get_interfaces.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_interfaces"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let member = function(class.inner.cast(), iter);
+
+        if member.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityClass {
+            inner: member.cast(),
+        }))
+    }
+
+    fn get_class_parent(&self, class: &UnityClass) -> Result<Option<UnityClass>, RuntimeError> {
+        let function = self.exports.mono_class_get_parent.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_parent"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let parent = function(class.inner.cast());
+
+        if parent.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityClass {
+            inner: parent.cast(),
+        }))
+    }
+
+    fn get_class_flags(&self, class: &UnityClass) -> Result<i32, RuntimeError> {
+        let function = self.exports.mono_class_get_flags.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_flags"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        Ok(function(class.inner.cast()) as i32)
+    }
+
+    fn is_class_generic(&self, class: &UnityClass) -> Result<bool, RuntimeError> {
+        let get_type = self.exports.mono_class_get_type.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_type"))?;
+        let type_get_type = self.exports.mono_type_get_type.as_ref().ok_or(RuntimeError::MissingFunction("mono_type_get_type"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let ty = get_type(class.inner.cast());
+
+        if ty.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_class_get_type"));
+        }
+
+        // the generic container isn't exported, so definitions like List<T> can't be told apart
+        Ok(type_get_type(ty) == MONO_TYPE_GENERICINST)
+    }
+
+    fn get_method(&self, class: &UnityClass, name: &str, param_count: i32) -> Result<UnityMethod, RuntimeError> {
+        let function = self.exports.mono_class_get_method_from_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_method_from_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let method_name = CString::new(name)?;
+
+        let method = function(class.inner.cast(), method_name.as_ptr(), param_count);
+
+        if method.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Method, name));
+        }
+
+        Ok(UnityMethod {
+            inner: method.cast(),
+        })
+    }
+
+    fn get_method_name(&self, method: &UnityMethod) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_method_get_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_method_get_name"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        c_str_to_string(function(method.inner.cast()), "mono_method_get_name")
+    }
+
+    fn get_method_class(&self, method: &UnityMethod) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.mono_method_get_class.as_ref().ok_or(RuntimeError::MissingFunction("mono_method_get_class"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let class = function(method.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_method_get_class"));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    fn get_method_flags(&self, method: &UnityMethod) -> Result<i32, RuntimeError> {
+        let function = self.exports.mono_method_get_flags.as_ref().ok_or(RuntimeError::MissingFunction("mono_method_get_flags"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        Ok(function(method.inner.cast(), ptr::null_mut()) as i32)
+    }
+
+    fn get_method_param_count(&self, method: &UnityMethod) -> Result<u32, RuntimeError> {
+        let function = self.exports.mono_signature_get_param_count.as_ref().ok_or(RuntimeError::MissingFunction("mono_signature_get_param_count"))?;
+
+        Ok(function(self.get_method_signature(method)?))
+    }
+
+    fn get_method_return_type(&self, method: &UnityMethod) -> Result<UnityType, RuntimeError> {
+        let function = self.exports.mono_signature_get_return_type.as_ref().ok_or(RuntimeError::MissingFunction("mono_signature_get_return_type"))?;
+
+        let ty = function(self.get_method_signature(method)?);
+
+        if ty.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_signature_get_return_type"));
+        }
+
+        Ok(UnityType {
+            inner: ty.cast(),
+        })
+    }
+
+    fn get_method_param_types(&self, method: &UnityMethod) -> Result<Vec<UnityType>, RuntimeError> {
+        let function = self.exports.mono_signature_get_params.as_ref().ok_or(RuntimeError::MissingFunction("mono_signature_get_params"))?;
+
+        let signature = self.get_method_signature(method)?;
+        let mut iter = ptr::null_mut();
+        let mut types = Vec::new();
+
+        loop {
+            let ty = function(signature, &mut iter);
+
+            if ty.is_null() {
+                break;
+            }
+
+            types.push(UnityType {
+                inner: ty.cast(),
+            });
+        }
+
+        Ok(types)
+    }
+
+    fn get_method_param_names(&self, method: &UnityMethod) -> Result<Vec<String>, RuntimeError> {
+        let mut names = vec![ptr::null(); self.get_method_param_count(method)? as usize];
+
+        if let Some(function) = &self.exports.mono_method_get_param_names {
+            function(method.inner.cast(), names.as_mut_ptr());
+        }
+
+        // the names belong to the metadata, unnamed parameters are left null, as are all without the export
+        names.into_iter().enumerate().map(|(index, name)| match name.is_null() {
+            true => Ok(format!("arg{}", index)),
+            false => c_str_to_string(name, "mono_method_get_param_names"),
+        }).collect()
+    }
+
+    fn get_method_pointer(&self, method: &UnityMethod) -> Result<MethodPointer, RuntimeError> {
+        let function = self.exports.mono_compile_method.as_ref().ok_or(RuntimeError::MissingFunction("mono_compile_method"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let pointer = function(method.inner.cast());
+
+        if pointer.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_compile_method"));
+        }
+
+        Ok(pointer)
+    }
+
+    fn new_exception(&self, namespace: &str, name: &str, message: &CStr) -> Result<UnityObject, RuntimeError> {
+        let get_corlib = self.exports.mono_get_corlib.as_ref().ok_or(RuntimeError::MissingFunction("mono_get_corlib"))?;
+        let from_name = self.exports.mono_exception_from_name_msg.as_ref().ok_or(RuntimeError::MissingFunction("mono_exception_from_name_msg"))?;
+
+        let corlib = get_corlib();
+
+        if corlib.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_get_corlib"));
+        }
+
+        let namespace_str = CString::new(namespace)?;
+        let name_str = CString::new(name)?;
+
+        let exception = from_name(corlib, namespace_str.as_ptr(), name_str.as_ptr(), message.as_ptr());
+
+        if exception.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Class, format!("{}.{}", namespace, name)));
+        }
+
+        Ok(UnityObject {
+            inner: exception.cast(),
+        })
+    }
+
+    unsafe fn raise_exception(&self, exception: UnityObject) -> Result<Infallible, RuntimeError> {
+        let raise = self.exports.mono_raise_exception.as_ref().ok_or(RuntimeError::MissingFunction("mono_raise_exception"))?;
+
+        if exception.inner.is_null() {
+            return Err(RuntimeError::NullPointer("exception"));
+        }
+
+        raise(exception.inner.cast());
+
+        Err(RuntimeError::ReturnedNull("mono_raise_exception"))
+    }
+
+    fn invoke_method(&self, method: &UnityMethod, instance: Option<&UnityObject>, args: &mut [*mut c_void]) -> Result<Option<UnityObject>, RuntimeError> {
+        let function = self.exports.mono_runtime_invoke.as_ref().ok_or(RuntimeError::MissingFunction("mono_runtime_invoke"))?;
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let instance = instance.map_or(ptr::null_mut(), |instance| instance.inner);
+        let params = match args.is_empty() {
+            true => ptr::null_mut(),
+            false => args.as_mut_ptr(),
+        };
+
+        let mut exception = ptr::null_mut();
+        let result = function(method.inner.cast(), instance.cast(), params, &mut exception);
+
+        if !exception.is_null() {
+            return Err(RuntimeError::ManagedException(self.read_exception(exception)));
+        }
+
+        match result.is_null() {
+            true => Ok(None),
+            false => Ok(Some(UnityObject { inner: result.cast() })),
+        }
+    }
+
+    fn get_field(&self, class: &UnityClass, name: &str) -> Result<UnityField, RuntimeError> {
+        let function = self.exports.mono_class_get_field_from_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_field_from_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let field_name = CString::new(name)?;
+
+        let field = function(class.inner.cast(), field_name.as_ptr());
+
+        if field.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Field, name));
+        }
+
+        Ok(UnityField {
+            inner: field.cast(),
+        })
+    }
+
+    fn get_field_name(&self, field: &UnityField) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_field_get_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_get_name"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        c_str_to_string(function(field.inner.cast()), "mono_field_get_name")
+    }
+
+    fn get_field_offset(&self, field: &UnityField) -> Result<usize, RuntimeError> {
+        let function = self.exports.mono_field_get_offset.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_get_offset"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        Ok(function(field.inner.cast()) as usize)
+    }
+
+    fn get_field_type(&self, field: &UnityField) -> Result<UnityType, RuntimeError> {
+        let function = self.exports.mono_field_get_type.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_get_type"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        let ty = function(field.inner.cast());
+
+        if ty.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_field_get_type"));
+        }
+
+        Ok(UnityType {
+            inner: ty.cast(),
+        })
+    }
+
+    fn get_field_flags(&self, field: &UnityField) -> Result<i32, RuntimeError> {
+        let function = self.exports.mono_field_get_flags.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_get_flags"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        Ok(function(field.inner.cast()) as i32)
+    }
+
+    fn get_field_value(&self, field: &UnityField, instance: &UnityObject, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_field_get_value.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_get_value"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        if instance.inner.is_null() {
+            return Err(RuntimeError::NullPointer("instance"));
+        }
+
+        function(instance.inner.cast(), field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn set_field_value(&self, field: &UnityField, instance: &UnityObject, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_field_set_value.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_set_value"))?;
+
+        if field.inner.is_null() {
+            return Err(RuntimeError::NullPointer("field"));
+        }
+
+        if instance.inner.is_null() {
+            return Err(RuntimeError::NullPointer("instance"));
+        }
+
+        function(instance.inner.cast(), field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn get_static_field_value(&self, field: &UnityField, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_field_static_get_value.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_static_get_value"))?;
+
+        let vtable = self.get_field_vtable(field)?;
+
+        function(vtable, field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn set_static_field_value(&self, field: &UnityField, value: *mut c_void) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_field_static_set_value.as_ref().ok_or(RuntimeError::MissingFunction("mono_field_static_set_value"))?;
+
+        let vtable = self.get_field_vtable(field)?;
+
+        function(vtable, field.inner.cast(), value);
+
+        Ok(())
+    }
+
+    fn get_type_name(&self, ty: &UnityType) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_type_get_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_type_get_name"))?;
+
+        if ty.inner.is_null() {
+            return Err(RuntimeError::NullPointer("ty"));
+        }
+
+        let name = function(ty.inner.cast());
+        let result = c_str_to_string(name, "mono_type_get_name");
+
+        self.free(name.cast());
+
+        result
+    }
+
+    fn get_type_class(&self, ty: &UnityType) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.mono_class_from_mono_type.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_from_mono_type"))?;
+
+        if ty.inner.is_null() {
+            return Err(RuntimeError::NullPointer("ty"));
+        }
+
+        let class = function(ty.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_class_from_mono_type"));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    fn get_type_size(&self, ty: &UnityType) -> Result<usize, RuntimeError> {
+        let function = self.exports.mono_type_size.as_ref().ok_or(RuntimeError::MissingFunction("mono_type_size"))?;
+
+        if ty.inner.is_null() {
+            return Err(RuntimeError::NullPointer("ty"));
+        }
+
+        let mut align = 0;
+        let size = function(ty.inner.cast(), &mut align);
+
+        Ok(size as usize)
+    }
+
+    fn get_corlib(&self) -> Result<UnityImage, RuntimeError> {
+        let function = self.exports.mono_get_corlib.as_ref().ok_or(RuntimeError::MissingFunction("mono_get_corlib"))?;
+
+        let image = function();
+
+        if image.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_get_corlib"));
+        }
+
+        Ok(UnityImage {
+            inner: image.cast(),
+        })
+    }
+
+    fn is_value_type(&self, class: &UnityClass) -> Result<bool, RuntimeError> {
+        let function = self.exports.mono_class_is_valuetype.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_is_valuetype"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        Ok(function(class.inner.cast()) != 0)
+    }
+
+    fn get_class_value_size(&self, class: &UnityClass) -> Result<usize, RuntimeError> {
+        let function = self.exports.mono_class_value_size.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_value_size"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let mut align = 0;
+        let size = function(class.inner.cast(), &mut align);
+
+        Ok(size as usize)
+    }
+
+    fn get_object_class(&self, object: &UnityObject) -> Result<UnityClass, RuntimeError> {
+        let function = self.exports.mono_object_get_class.as_ref().ok_or(RuntimeError::MissingFunction("mono_object_get_class"))?;
+
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        let class = function(object.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_object_get_class"));
+        }
+
+        Ok(UnityClass {
+            inner: class.cast(),
+        })
+    }
+
+    fn is_instance_of(&self, object: &UnityObject, class: &UnityClass) -> Result<bool, RuntimeError> {
+        let function = self.exports.mono_object_isinst.as_ref().ok_or(RuntimeError::MissingFunction("mono_object_isinst"))?;
+
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        Ok(!function(object.inner.cast(), class.inner.cast()).is_null())
+    }
+
+    fn new_object(&self, class: &UnityClass) -> Result<UnityObject, RuntimeError> {
+        let function = self.exports.mono_object_new.as_ref().ok_or(RuntimeError::MissingFunction("mono_object_new"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let domain = self.get_domain()?;
+        let object = function(domain.inner.cast(), class.inner.cast());
+
+        if object.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_object_new"));
+        }
+
+        Ok(UnityObject {
+            inner: object.cast(),
+        })
+    }
+
+    fn box_value(&self, class: &UnityClass, value: *mut c_void) -> Result<UnityObject, RuntimeError> {
+        let function = self.exports.mono_value_box.as_ref().ok_or(RuntimeError::MissingFunction("mono_value_box"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        if value.is_null() {
+            return Err(RuntimeError::NullPointer("value"));
+        }
+
+        let domain = self.get_domain()?;
+        let object = function(domain.inner.cast(), class.inner.cast(), value);
+
+        if object.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_value_box"));
+        }
+
+        Ok(UnityObject {
+            inner: object.cast(),
+        })
+    }
+
+    fn unbox_value(&self, object: &UnityObject) -> Result<*mut c_void, RuntimeError> {
+        let function = self.exports.mono_object_unbox.as_ref().ok_or(RuntimeError::MissingFunction("mono_object_unbox"))?;
+
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        let value = function(object.inner.cast());
+
+        if value.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_object_unbox"));
+        }
+
+        Ok(value)
+    }
+
+    fn get_virtual_method(&self, object: &UnityObject, method: &UnityMethod) -> Result<UnityMethod, RuntimeError> {
+        let function = self.exports.mono_object_get_virtual_method.as_ref().ok_or(RuntimeError::MissingFunction("mono_object_get_virtual_method"))?;
+
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        if method.inner.is_null() {
+            return Err(RuntimeError::NullPointer("method"));
+        }
+
+        let method = function(object.inner.cast(), method.inner.cast());
+
+        if method.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_object_get_virtual_method"));
+        }
+
+        Ok(UnityMethod {
+            inner: method.cast(),
+        })
+    }
+
+    fn object_to_string(&self, object: &UnityObject) -> Result<String, RuntimeError> {
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        // missing on old mono, where ToString is called like any other method
+        let Some(function) = self.exports.mono_object_to_string.as_ref() else {
+            return object::invoke_to_string(self, object);
+        };
+
+        let mut exception = ptr::null_mut();
+        let string = function(object.inner.cast(), &mut exception);
+
+        if !exception.is_null() {
+            return Err(RuntimeError::ManagedException(self.read_exception(exception)));
+        }
+
+        if string.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_object_to_string"));
+        }
+
+        self.string_to_utf8(&UnityString {
+            inner: string.cast(),
+        })
+    }
+
+    fn new_array(&self, element_class: &UnityClass, len: usize) -> Result<UnityObject, RuntimeError> {
+        let function = self.exports.mono_array_new.as_ref().ok_or(RuntimeError::MissingFunction("mono_array_new"))?;
+
+        if element_class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("element_class"));
+        }
+
+        let domain = self.get_domain()?;
+        let array = function(domain.inner.cast(), element_class.inner.cast(), len);
+
+        if array.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_array_new"));
+        }
+
+        Ok(UnityObject {
+            inner: array.cast(),
+        })
+    }
+
+    fn get_array_length(&self, array: &UnityObject) -> Result<usize, RuntimeError> {
+        let function = self.exports.mono_array_length.as_ref().ok_or(RuntimeError::MissingFunction("mono_array_length"))?;
+
+        if array.inner.is_null() {
+            return Err(RuntimeError::NullPointer("array"));
+        }
+
+        Ok(function(array.inner.cast()))
+    }
+
+    fn get_array_element_size(&self, array: &UnityObject) -> Result<usize, RuntimeError> {
+        let class_function = self.exports.mono_object_get_class.as_ref().ok_or(RuntimeError::MissingFunction("mono_object_get_class"))?;
+        let size_function = self.exports.mono_array_element_size.as_ref().ok_or(RuntimeError::MissingFunction("mono_array_element_size"))?;
+
+        if array.inner.is_null() {
+            return Err(RuntimeError::NullPointer("array"));
+        }
+
+        let class = class_function(array.inner.cast());
+
+        if class.is_null() {
+            return Err(RuntimeError::ReturnedNull("mono_object_get_class"));
+        }
+
+        Ok(size_function(class) as usize)
+    }
+
+    unsafe fn set_array_reference(&self, array: &UnityObject, slot: *mut *mut c_void, value: &UnityObject) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_gc_wbarrier_set_arrayref.as_ref().ok_or(RuntimeError::MissingFunction("mono_gc_wbarrier_set_arrayref"))?;
+
+        if array.inner.is_null() {
+            return Err(RuntimeError::NullPointer("array"));
+        }
+
+        function(array.inner.cast(), slot.cast(), value.inner.cast());
+
+        Ok(())
+    }
+
+    fn new_gc_handle(&self, object: &UnityObject, kind: GcHandleKind) -> Result<usize, RuntimeError> {
+        if object.inner.is_null() {
+            return Err(RuntimeError::NullPointer("object"));
+        }
+
+        let handle = match kind {
+            GcHandleKind::Weak => {
+                let function = self.exports.mono_gchandle_new_weakref.as_ref().ok_or(RuntimeError::MissingFunction("mono_gchandle_new_weakref"))?;
+                function(object.inner.cast(), 0)
+            }
+            GcHandleKind::Strong | GcHandleKind::Pinned => {
+                let function = self.exports.mono_gchandle_new.as_ref().ok_or(RuntimeError::MissingFunction("mono_gchandle_new"))?;
+                function(object.inner.cast(), (kind == GcHandleKind::Pinned) as i32)
+            }
+        };
+
+        if handle == 0 {
+            return Err(RuntimeError::ReturnedNull("mono_gchandle_new"));
+        }
+
+        Ok(handle as usize)
+    }
+
+    fn get_gc_handle_target(&self, handle: usize) -> Result<Option<UnityObject>, RuntimeError> {
+        let function = self.exports.mono_gchandle_get_target.as_ref().ok_or(RuntimeError::MissingFunction("mono_gchandle_get_target"))?;
+
+        let object = function(handle as u32);
+
+        match object.is_null() {
+            true => Ok(None),
+            false => Ok(Some(UnityObject {
+                inner: object.cast(),
+            })),
+        }
+    }
+
+    fn free_gc_handle(&self, handle: usize) -> Result<(), RuntimeError> {
+        let function = self.exports.mono_gchandle_free.as_ref().ok_or(RuntimeError::MissingFunction("mono_gchandle_free"))?;
+
+        function(handle as u32);
+
+        Ok(())
+    }
+
+    fn get_property(&self, class: &UnityClass, name: &str) -> Result<UnityProperty, RuntimeError> {
+        let function = self.exports.mono_class_get_property_from_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_class_get_property_from_name"))?;
+
+        if class.inner.is_null() {
+            return Err(RuntimeError::NullPointer("class"));
+        }
+
+        let property_name = CString::new(name)?;
+
+        let property = function(class.inner.cast(), property_name.as_ptr());
+
+        if property.is_null() {
+            return Err(RuntimeError::not_found(LookupKind::Property, name));
+        }
+
+        Ok(UnityProperty {
+            inner: property.cast(),
+        })
+    }
+
+    fn get_property_name(&self, property: &UnityProperty) -> Result<String, RuntimeError> {
+        let function = self.exports.mono_property_get_name.as_ref().ok_or(RuntimeError::MissingFunction("mono_property_get_name"))?;
+
+        if property.inner.is_null() {
+            return Err(RuntimeError::NullPointer("property"));
+        }
+
+        c_str_to_string(function(property.inner.cast()), "mono_property_get_name")
+    }
+
+    fn get_property_get_method(&self, property: &UnityProperty) -> Result<Option<UnityMethod>, RuntimeError> {
+        let function = self.exports.mono_property_get_get_method.as_ref().ok_or(RuntimeError::MissingFunction("mono_property_get_get_method"))?;
+
+        if property.inner.is_null() {
+            return Err(RuntimeError::NullPointer("property"));
+        }
+
+        let method = function(property.inner.cast());
+
+        if method.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityMethod {
+            inner: method.cast(),
+        }))
+    }
+
+    fn get_property_set_method(&self, property: &UnityProperty) -> Result<Option<UnityMethod>, RuntimeError> {
+        let function = self.exports.mono_property_get_set_method.as_ref().ok_or(RuntimeError::MissingFunction("mono_property_get_set_method"))?;
+
+        if property.inner.is_null() {
+            return Err(RuntimeError::NullPointer("property"));
+        }
+
+        let method = function(property.inner.cast());
+
+        if method.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(UnityMethod {
+            inner: method.cast(),
+        }))
+    }
+}
+
+/// stands in for `mono_jit_init_version`, see [`Runtime::on_initialized`]
+extern "C" fn jit_init_version_detour(domain_name: *const c_char, runtime_version: *const c_char) -> *mut MonoDomain {
+    let Some(original) = init::original::<extern "C" fn(*const c_char, *const c_char) -> *mut MonoDomain>() else {
+        return ptr::null_mut();
+    };
+
+    let domain = original(domain_name, runtime_version);
+
+    if !domain.is_null() {
+        init::initialized(UnityDomain {
+            inner: domain.cast(),
+        });
+    }
+
+    domain
+}
+
+/// the preload hook mono calls, forwarding to the [`AssemblyResolver`] in `user_data`
+extern "C" fn preload_trampoline(name: *mut AssemblyName, _assemblies_path: *mut *mut c_char, user_data: *mut c_void) -> *mut MonoAssembly {
+    resolve_assembly("assembly preload hook", name, user_data)
+}
+
+/// the search hook mono calls, forwarding to the [`AssemblyResolver`] in `user_data`
+extern "C" fn search_trampoline(name: *mut AssemblyName, user_data: *mut c_void) -> *mut MonoAssembly {
+    resolve_assembly("assembly search hook", name, user_data)
+}
+
+/// the load hook mono calls, forwarding to the [`AssemblyLoadCallback`] in `user_data`
+extern "C" fn load_trampoline(assembly: *mut MonoAssembly, user_data: *mut c_void) {
+    if assembly.is_null() || user_data.is_null() {
+        return;
+    }
+
+    let on_load = unsafe { &*user_data.cast::<AssemblyLoadCallback>() };
+
+    callback::catch("assembly load hook", || {
+        on_load(&UnityAssembly {
+            inner: assembly.cast(),
+        })
+    });
+}
+
+fn resolve_assembly(hook: &str, name: *mut AssemblyName, user_data: *mut c_void) -> *mut MonoAssembly {
+    if name.is_null() || user_data.is_null() {
+        return ptr::null_mut();
+    }
+
+    let resolver = unsafe { &*user_data.cast::<AssemblyResolver>() };
+
+    match callback::catch(hook, || resolver(unsafe { &*name })) {
+        Some(Some(assembly)) => assembly.inner.cast(),
+        _ => ptr::null_mut(),
+    }
 }
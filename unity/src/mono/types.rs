@@ -1,8 +1,8 @@
 //! TODO
 
-use std::{ffi::*, ptr::{addr_of_mut, addr_of}, error};
+use std::ffi::*;
 
-use thiserror::Error;
+use crate::{runtime::RuntimeError, utils::ffi::c_str_to_string};
 
 /// the appdomain
 #[derive(Debug)]
@@ -14,89 +14,60 @@ pub struct MonoDomain {}
 #[repr(C)]
 pub struct MonoThread {}
 
-#[derive(Debug, Error)]
-pub enum MonoMethodError {
-    #[error("not running under mono")]
-    NotMono,
-    #[error("failed to get runtime")]
-    GetRuntimeFailed,
-    #[error("failed to get assembly")]
-    GetImageFailed,
-    #[error("Missing Function: {0}")]
-    MissingFunction(String),
-    #[error("Failed to create CString: {0}")]
-    CStringCreationFailed(String),
-    #[error("Method returned null: {0}")]
-    NullMethod(String),
-    #[error("Managed Exception: {0}")]
-    ManagedException(String),
-}
-
 /// a method
 #[derive(Debug)]
 #[repr(C)]
 pub struct MonoMethod {}
 
-#[derive(Debug, Error)]
-pub enum MonoClassError {
-    #[error("not running under mono")]
-    NotMono,
-    #[error("failed to get runtime")]
-    GetRuntimeFailed,
-    #[error("failed to get assembly")]
-    GetImageFailed,
-    #[error("Missing Function: {0}")]
-    MissingFunction(String),
-    #[error("Failed to create CString: {0}")]
-    CStringCreationFailed(String),
-    #[error("Method returned null: {0}")]
-    NullMethod(String),
-}
+/// a method signature
+#[derive(Debug)]
+#[repr(C)]
+pub struct MonoMethodSignature {}
 
 /// a class
 #[derive(Debug)]
 #[repr(C)]
 pub struct MonoClass {}
 
-#[derive(Debug, Error)]
-pub enum MonoAssemblyError {
-    #[error("not running under mono")]
-    NotMono,
-    #[error("failed to get runtime")]
-    GetRuntimeFailed,
-    #[error("failed to get assembly")]
-    GetImageFailed,
-    #[error("Missing Function: {0}")]
-    MissingFunction(String),
-    #[error("Failed to create C-String {0}")]
-    CStringCreationFailed(String),
-    #[error("Failed to get Domain")]
-    GetDomainFailed,
-}
+/// a class vtable
+#[derive(Debug)]
+#[repr(C)]
+pub struct MonoVTable {}
+
+/// a field
+#[derive(Debug)]
+#[repr(C)]
+pub struct MonoClassField {}
+
+/// a type
+#[derive(Debug)]
+#[repr(C)]
+pub struct MonoType {}
+
+/// a property
+#[derive(Debug)]
+#[repr(C)]
+pub struct MonoProperty {}
+
+/// an exception
+#[derive(Debug)]
+#[repr(C)]
+pub struct MonoException {}
 
 /// an assembly
 #[derive(Debug)]
 #[repr(C)]
 pub struct MonoAssembly {}
 
-#[derive(Debug, Error)]
-pub enum MonoImageError {
-    #[error("not running under mono")]
-    NotMono,
-    #[error("failed to get runtime")]
-    GetRuntimeFailed,
-    #[error("failed to get image")]
-    GetImageFailed,
-    #[error("Missing Function: {0}")]
-    MissingFunction(String),
-    #[error("Failed to get Assembly: {0}")]
-    GetAssemblyFailed(String),
-}
-
 /// a mono image
 #[derive(Debug)]
 #[repr(C)]
 pub struct MonoImage {}
+/// a mono array
+#[derive(Debug)]
+#[repr(C)]
+pub struct MonoArray {}
+
 /// a mono string
 #[derive(Debug)]
 #[repr(C)]
@@ -125,7 +96,7 @@ pub struct MonoReflectionAssembly {
 }
 
 /// an assembly name
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 #[repr(C)]
 pub struct AssemblyName {
     pub name: *mut c_char,
@@ -146,16 +117,9 @@ pub struct AssemblyName {
     pub arch: u32,
 }
 
-#[derive(Debug, Error)]
-pub enum MonoStringError {
-    #[error("not running under mono")]
-    NotMono,
-    #[error("mono_string_to_utf8 failed")]
-    Utf8Failed,
-    #[error("failed to create string")]
-    CreateFailed,
-    #[error("failed to get runtime")]
-    GetRuntimeFailed,
-    #[error("Failed to get Domain")]
-    GetDomainFailed,
-}
\ No newline at end of file
+impl AssemblyName {
+    /// returns the simple name of the assembly, e.g. `Assembly-CSharp`
+    pub fn get_name(&self) -> Result<String, RuntimeError> {
+        c_str_to_string(self.name, "AssemblyName::name")
+    }
+}
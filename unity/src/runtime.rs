@@ -1,80 +1,398 @@
 //! TODO
 
-use std::{error, path::PathBuf, io};
+use std::{path::Path, io, sync::OnceLock, ffi::{c_void, CStr, NulError}, fmt::{self, Display}, convert::Infallible};
 
 use thiserror::Error;
 
 use crate::{
-    common::{domain::UnityDomain, thread::UnityThread, method::{MethodPointer}},
+    common::{exception::ManagedException, gc_handle::GcHandleKind, init::InitCallback, domain::UnityDomain, thread::UnityThread, method::{MethodPointer}, string::UnityString, class::UnityClass, method::UnityMethod, object::UnityObject, assembly::UnityAssembly, image::UnityImage, field::UnityField, property::UnityProperty, ty::UnityType},
+    hook::HookError,
     il2cpp::Il2Cpp,
-    mono::{Mono, AssemblyHookType},
-    utils, libs::{self, NativeMethod},
+    mono::{Mono, AssemblyHookType, types::AssemblyName},
+    utils::{self, path::PathError}, libs::{ExportReport, LibError},
 };
 
+/// the error type of the crate
+///
+/// every fallible api returns this, so callers can match on the kind of failure directly
+///
+/// # Examples
+///
+/// ```
+/// use unity_rs::{libs::{self, LibError}, runtime::RuntimeError};
+///
+/// let error: RuntimeError = libs::load_lib("/does/not/exist.so").unwrap_err().into();
+///
+/// match error {
+///     RuntimeError::Lib(LibError::FailedToLoadLib { reason, .. }) => assert!(!reason.is_empty()),
+///     error => panic!("unexpected error: {}", error),
+/// }
+/// ```
 #[derive(Debug, Error)]
 pub enum RuntimeError {
+    /// an io error, e.g. while looking up the current executable
     #[error(transparent)]
     Io(#[from] io::Error),
+    /// loading a native library or resolving its exports failed
     #[error(transparent)]
-    Fmt(#[from] std::fmt::Error),
+    Lib(#[from] LibError),
+    /// installing or removing a hook failed
     #[error(transparent)]
-    Std(#[from] Box<dyn error::Error>),
+    Hook(#[from] HookError),
+    /// the game or its runtime couldn't be located on disk
     #[error(transparent)]
-    Lib(#[from] libs::LibError),
+    Path(#[from] PathError),
+    /// a string handed to the runtime contained a nul byte
     #[error(transparent)]
-    Nul(#[from] std::ffi::NulError),
+    Nul(#[from] NulError),
+    /// managed code threw an exception
+    #[error("Managed Exception: {0}")]
+    ManagedException(ManagedException),
 
     #[error("Not a unity process")]
     NotUnity,
-    #[error("Failed to find Base Path")]
-    BasePathNotFound,
-    #[error("Data Path not found!")]
-    DataPathNotFound,
-    #[error("Failed to find mono library path")]
-    MonoLibPath,
-    #[error("Failed to get mono lib name")]
-    MonoLibName,
-    #[error("Missing version argument in mono_jit_init_version")]
-    JitInitVersionArgMissing,
     #[error("Function '{0}' not found")]
     MissingFunction(&'static str),
     #[error("Function Returned Null at {0}")]
     ReturnedNull(&'static str),
-    #[error("Failed to get Game Assembly")]
-    GameAssemblyNotFound,
-    #[error("Failed to initialize Runtime")]
-    FailedToInitRuntime,
-    #[error("Failed to create C-String")]
-    FailedToCreateCString,
-    #[error("{0}")]
-    Passthrough(String),
-    #[error("String may not be empty!")]
-    EmptyString,
     #[error("Argument {0} is a null pointer!")]
     NullPointer(&'static str),
+    #[error("{kind} not found: {name}")]
+    NotFound { kind: LookupKind, name: String },
+    #[error("Expected {expected} arguments, got {got}")]
+    ArgumentCount { expected: u32, got: usize },
+    #[error("Field {0} is static")]
+    FieldIsStatic(String),
+    #[error("Field {0} is not static")]
+    FieldNotStatic(String),
+    #[error("Field is {expected} bytes, but the value is {got} bytes")]
+    SizeMismatch { expected: usize, got: usize },
+    #[error("Property {0} has no getter")]
+    NoGetter(String),
+    #[error("Property {0} has no setter")]
+    NoSetter(String),
+    #[error("Invalid string: {0}")]
+    InvalidString(&'static str),
+    #[error("Invalid internal call name '{0}', expected Namespace.Class::Method")]
+    InvalidInternalCallName(String),
+    #[error("The current thread isn't attached to the runtime")]
+    ThreadNotAttached,
+    #[error("The main thread hasn't been set")]
+    MainThreadNotSet,
+    #[error("Dispatched work panicked")]
+    DispatchPanicked,
+    #[error("Index {index} is out of range for length {len}")]
+    IndexOutOfRange { index: usize, len: usize },
+    #[error("No overload of {name} matches, candidates: {}", candidates.join(", "))]
+    NoMatchingOverload { name: String, candidates: Vec<String> },
+    #[error("Class {0} is not a value type")]
+    NotValueType(String),
+    #[error("Method {0} has an unsupported signature")]
+    UnsupportedSignature(String),
+    #[error("String may not be empty!")]
+    EmptyString,
     #[error("Not Implemented: {0}")]
     NotImplemented(&'static str),
 }
 
+/// what a failed lookup was looking for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookupKind {
+    Assembly,
+    Class,
+    Method,
+    Field,
+    Property,
+}
+
+impl Display for LookupKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupKind::Assembly => write!(f, "Assembly"),
+            LookupKind::Class => write!(f, "Class"),
+            LookupKind::Method => write!(f, "Method"),
+            LookupKind::Field => write!(f, "Field"),
+            LookupKind::Property => write!(f, "Property"),
+        }
+    }
+}
+
+impl RuntimeError {
+    /// shorthand for [`RuntimeError::NotFound`]
+    pub(crate) fn not_found(kind: LookupKind, name: impl Into<String>) -> RuntimeError {
+        RuntimeError::NotFound {
+            kind,
+            name: name.into(),
+        }
+    }
+}
+
 pub enum RuntimeType<'a> {
     Mono(&'a Mono),
     Il2Cpp(&'a Il2Cpp)
 }
 
-pub trait Runtime {
-    fn get_type(&self) -> RuntimeType;
+pub trait Runtime: Send + Sync {
+    fn get_type(&self) -> RuntimeType<'_>;
     fn get_domain(&self) -> Result<UnityDomain, RuntimeError>;
+
+    /// returns the current thread
+    ///
+    /// fails with `RuntimeError::ThreadNotAttached` on threads that aren't attached to the runtime.
     fn get_current_thread(&self) -> Result<UnityThread, RuntimeError>;
+
+    /// marks `thread` as the main thread, which unity runs the game on
     fn set_main_thread(&self, thread: UnityThread) -> Result<(), RuntimeError>;
-    fn attach_to_thread(&self, thread: UnityDomain) -> Result<UnityThread, RuntimeError>;
+
+    /// returns the thread marked with [`set_main_thread`](Runtime::set_main_thread)
+    ///
+    /// without one, mono asks the runtime for its main thread, while il2cpp uses the thread that ran
+    /// `il2cpp_init` if [`on_initialized`](Runtime::on_initialized) saw it.
+    ///
+    /// # Errors
+    ///
+    /// * `RuntimeError::MainThreadNotSet` - if no main thread is known yet
+    fn main_thread(&self) -> Result<UnityThread, RuntimeError>;
+
+    /// whether the current thread is the main thread, see [`main_thread`](Runtime::main_thread)
+    ///
+    /// threads not attached to the runtime aren't the main thread.
+    fn is_main_thread(&self) -> Result<bool, RuntimeError> {
+        let main_thread = self.main_thread()?;
+
+        Ok(self.get_current_thread().is_ok_and(|thread| thread.inner == main_thread.inner))
+    }
+
+    /// attaches the current thread to `domain`, so it can call managed code
+    ///
+    /// prefer [`AttachedThread`](crate::common::thread::AttachedThread), which detaches again.
+    fn attach_to_thread(&self, domain: UnityDomain) -> Result<UnityThread, RuntimeError>;
+
+    /// detaches `thread` from the runtime, which has to be the current thread
+    fn detach_from_thread(&self, thread: UnityThread) -> Result<(), RuntimeError>;
+
+    /// registers `func` as the implementation of the internal call `name`
+    ///
+    /// `name` has to be in the `Namespace.Class::Method` format, see [`validate_name`](crate::common::internal_call::validate_name).
+    /// `func` has to match the managed signature exactly, which [`internal_call!`](crate::internal_call) takes care of.
     fn add_internal_call(&self, name: String, func: MethodPointer) -> Result<(), RuntimeError>;
-    fn install_assembly_hook(&self, hook_type: AssemblyHookType, func: MethodPointer) -> Result<(), RuntimeError>;
+
+    /// installs a hook on assembly loading, see [`AssemblyHookType`]
+    ///
+    /// the hook stays installed for the rest of the process.
+    /// il2cpp has no such hooks, so there `Load` is run for every assembly once the runtime has initialized, see [`Runtime::on_initialized`],
+    /// and `Preload` and `Search` hook `il2cpp_domain_assembly_open`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::{mono::AssemblyHookType, runtime::get_runtime};
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// get_runtime()?.install_assembly_hook(AssemblyHookType::Load(Box::new(|assembly| {
+    ///     if let Ok(name) = assembly.name().and_then(|name| name.get_name()) {
+    ///         println!("loaded {}", name);
+    ///     }
+    /// })))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn install_assembly_hook(&self, hook_type: AssemblyHookType) -> Result<(), RuntimeError>;
+
     fn get_export_ptr(&self, name: &str) -> Result<MethodPointer, RuntimeError>;
+
+    /// runs `callback` with the root domain once the runtime has initialized
+    ///
+    /// hooks `mono_jit_init_version` or `il2cpp_init` to find out when the game initializes the runtime.
+    /// callbacks registered once it's initialized run right away.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unity_rs::runtime::get_runtime;
+    ///
+    /// # fn main() -> Result<(), unity_rs::runtime::RuntimeError> {
+    /// get_runtime()?.on_initialized(Box::new(|domain| {
+    ///     let assemblies = domain.assemblies();
+    /// }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn on_initialized(&self, callback: InitCallback) -> Result<(), RuntimeError>;
+    /// reports which exports of the runtime were found, aliased or missing
+    fn get_export_report(&self) -> &ExportReport;
+    fn new_string(&self, value: &str) -> Result<UnityString, RuntimeError>;
+    fn new_string_utf16(&self, value: &[u16]) -> Result<UnityString, RuntimeError>;
+    fn string_to_utf8(&self, string: &UnityString) -> Result<String, RuntimeError>;
+    fn string_to_utf16(&self, string: &UnityString) -> Result<Vec<u16>, RuntimeError>;
+    fn get_assemblies(&self, domain: &UnityDomain) -> Result<Vec<UnityAssembly>, RuntimeError>;
+    fn open_assembly(&self, domain: &UnityDomain, path: &str) -> Result<UnityAssembly, RuntimeError>;
+    fn get_assembly_image(&self, assembly: &UnityAssembly) -> Result<UnityImage, RuntimeError>;
+    fn get_assembly_name(&self, assembly: &UnityAssembly) -> Result<AssemblyName, RuntimeError>;
+    fn get_image_name(&self, image: &UnityImage) -> Result<String, RuntimeError>;
+    fn get_image_filename(&self, image: &UnityImage) -> Result<String, RuntimeError>;
+    fn get_class_from_image(&self, image: &UnityImage, namespace: &str, name: &str) -> Result<UnityClass, RuntimeError>;
+
+    /// looks up a class by the name of its assembly, its namespace and its name
+    fn get_class(&self, assembly: &str, namespace: &str, name: &str) -> Result<UnityClass, RuntimeError> {
+        let domain = self.get_domain()?;
+        let image = self.get_assembly_image(&self.open_assembly(&domain, assembly)?)?;
+
+        self.get_class_from_image(&image, namespace, name).map_err(|error| match error {
+            RuntimeError::NotFound { kind: LookupKind::Class, name } => RuntimeError::not_found(LookupKind::Class, format!("{} in {}", name, assembly)),
+            error => error,
+        })
+    }
+    fn get_class_name(&self, class: &UnityClass) -> Result<String, RuntimeError>;
+    fn get_class_namespace(&self, class: &UnityClass) -> Result<String, RuntimeError>;
+
+    /// returns the next method declared on `class`, `None` once all were returned
+    ///
+    /// `iter` is the runtime's cursor, starting out null, prefer [`UnityClass::methods`](crate::common::class::UnityClass::methods)
+    fn next_class_method(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityMethod>, RuntimeError>;
+
+    /// returns the next field declared on `class`, see [`Runtime::next_class_method`]
+    fn next_class_field(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityField>, RuntimeError>;
+
+    /// returns the next property declared on `class`, see [`Runtime::next_class_method`]
+    fn next_class_property(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityProperty>, RuntimeError>;
+
+    /// returns the next class nested in `class`, see [`Runtime::next_class_method`]
+    fn next_nested_type(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityClass>, RuntimeError>;
+
+    /// returns the next interface `class` implements itself, see [`Runtime::next_class_method`]
+    fn next_class_interface(&self, class: &UnityClass, iter: &mut *mut c_void) -> Result<Option<UnityClass>, RuntimeError>;
+
+    /// returns the class `class` derives from, `None` for `System.Object` and interfaces
+    fn get_class_parent(&self, class: &UnityClass) -> Result<Option<UnityClass>, RuntimeError>;
+
+    /// returns the attribute flags of `class`
+    fn get_class_flags(&self, class: &UnityClass) -> Result<i32, RuntimeError>;
+
+    /// returns whether `class` is generic, either a definition like `List<T>` or an instance like `List<int>`
+    ///
+    /// mono only recognizes instances, since it doesn't expose whether a class is a generic definition.
+    fn is_class_generic(&self, class: &UnityClass) -> Result<bool, RuntimeError>;
+    fn get_method(&self, class: &UnityClass, name: &str, param_count: i32) -> Result<UnityMethod, RuntimeError>;
+    fn get_method_name(&self, method: &UnityMethod) -> Result<String, RuntimeError>;
+
+    /// returns the class declaring `method`
+    fn get_method_class(&self, method: &UnityMethod) -> Result<UnityClass, RuntimeError>;
+
+    /// returns the attribute flags of `method`
+    fn get_method_flags(&self, method: &UnityMethod) -> Result<i32, RuntimeError>;
+    fn get_method_param_count(&self, method: &UnityMethod) -> Result<u32, RuntimeError>;
+
+    /// returns the return type of `method`, `System.Void` if it returns nothing
+    fn get_method_return_type(&self, method: &UnityMethod) -> Result<UnityType, RuntimeError>;
+
+    /// returns the types of the parameters of `method`, in order
+    fn get_method_param_types(&self, method: &UnityMethod) -> Result<Vec<UnityType>, RuntimeError>;
+
+    /// returns the names of the parameters of `method`, in order, `argN` where the metadata has none or the runtime can't look them up
+    fn get_method_param_names(&self, method: &UnityMethod) -> Result<Vec<String>, RuntimeError>;
+    fn get_method_pointer(&self, method: &UnityMethod) -> Result<MethodPointer, RuntimeError>;
+
+    /// creates an exception of the corlib class `namespace.name`, to be raised with [`raise_exception`](Runtime::raise_exception)
+    fn new_exception(&self, namespace: &str, name: &str, message: &CStr) -> Result<UnityObject, RuntimeError>;
+
+    /// raises `exception` in the managed code that called into rust
+    ///
+    /// never returns once the exception is raised, only if the runtime can't raise it.
+    ///
+    /// # Safety
+    ///
+    /// see [`callback::catch_managed`](crate::common::callback::catch_managed)
+    unsafe fn raise_exception(&self, exception: UnityObject) -> Result<Infallible, RuntimeError>;
+    fn invoke_method(&self, method: &UnityMethod, instance: Option<&UnityObject>, args: &mut [*mut c_void]) -> Result<Option<UnityObject>, RuntimeError>;
+    fn get_field(&self, class: &UnityClass, name: &str) -> Result<UnityField, RuntimeError>;
+    fn get_field_name(&self, field: &UnityField) -> Result<String, RuntimeError>;
+    fn get_field_offset(&self, field: &UnityField) -> Result<usize, RuntimeError>;
+    fn get_field_type(&self, field: &UnityField) -> Result<UnityType, RuntimeError>;
+    fn get_field_flags(&self, field: &UnityField) -> Result<i32, RuntimeError>;
+    fn get_field_value(&self, field: &UnityField, instance: &UnityObject, value: *mut c_void) -> Result<(), RuntimeError>;
+    fn set_field_value(&self, field: &UnityField, instance: &UnityObject, value: *mut c_void) -> Result<(), RuntimeError>;
+    fn get_static_field_value(&self, field: &UnityField, value: *mut c_void) -> Result<(), RuntimeError>;
+    fn set_static_field_value(&self, field: &UnityField, value: *mut c_void) -> Result<(), RuntimeError>;
+    fn get_property(&self, class: &UnityClass, name: &str) -> Result<UnityProperty, RuntimeError>;
+    fn get_property_name(&self, property: &UnityProperty) -> Result<String, RuntimeError>;
+    fn get_property_get_method(&self, property: &UnityProperty) -> Result<Option<UnityMethod>, RuntimeError>;
+    fn get_property_set_method(&self, property: &UnityProperty) -> Result<Option<UnityMethod>, RuntimeError>;
+    fn get_type_name(&self, ty: &UnityType) -> Result<String, RuntimeError>;
+    fn get_type_class(&self, ty: &UnityType) -> Result<UnityClass, RuntimeError>;
+    fn get_type_size(&self, ty: &UnityType) -> Result<usize, RuntimeError>;
+
+    /// returns the image of the core library, `mscorlib`
+    fn get_corlib(&self) -> Result<UnityImage, RuntimeError>;
+
+    /// returns whether `class` is a value type
+    fn is_value_type(&self, class: &UnityClass) -> Result<bool, RuntimeError>;
+
+    /// returns the size of an unboxed instance of the value type `class`
+    fn get_class_value_size(&self, class: &UnityClass) -> Result<usize, RuntimeError>;
+
+    /// returns the class of `object`
+    fn get_object_class(&self, object: &UnityObject) -> Result<UnityClass, RuntimeError>;
+
+    /// returns whether `object` is an instance of `class`, or of a class deriving from or implementing it
+    fn is_instance_of(&self, object: &UnityObject, class: &UnityClass) -> Result<bool, RuntimeError>;
+
+    /// allocates an instance of `class`, without running a constructor
+    fn new_object(&self, class: &UnityClass) -> Result<UnityObject, RuntimeError>;
+
+    /// boxes the value type `class`, copied from `value`
+    fn box_value(&self, class: &UnityClass, value: *mut c_void) -> Result<UnityObject, RuntimeError>;
+
+    /// returns a pointer to the value inside the boxed `object`
+    fn unbox_value(&self, object: &UnityObject) -> Result<*mut c_void, RuntimeError>;
+
+    /// returns the override of the virtual `method` that `object` would call
+    fn get_virtual_method(&self, object: &UnityObject, method: &UnityMethod) -> Result<UnityMethod, RuntimeError>;
+
+    /// calls `ToString` on `object`
+    fn object_to_string(&self, object: &UnityObject) -> Result<String, RuntimeError>;
+
+    /// creates an array of `len` elements of `element_class`, prefer [`UnityArray`](crate::common::array::UnityArray)
+    fn new_array(&self, element_class: &UnityClass, len: usize) -> Result<UnityObject, RuntimeError>;
+
+    /// returns the number of elements in `array`
+    fn get_array_length(&self, array: &UnityObject) -> Result<usize, RuntimeError>;
+
+    /// returns the size of one element of `array`, the size of a pointer for reference types
+    fn get_array_element_size(&self, array: &UnityObject) -> Result<usize, RuntimeError>;
+
+    /// stores `value` in the element slot `slot` of `array`, going through the gc write barrier
+    ///
+    /// # Safety
+    ///
+    /// `slot` has to point to an element of `array`, and `array` has to hold object references
+    unsafe fn set_array_reference(&self, array: &UnityObject, slot: *mut *mut c_void, value: &UnityObject) -> Result<(), RuntimeError>;
+
+    /// creates a gc handle to `object`, prefer [`GcHandle`](crate::common::gc_handle::GcHandle) which frees it
+    fn new_gc_handle(&self, object: &UnityObject, kind: GcHandleKind) -> Result<usize, RuntimeError>;
+
+    /// returns the object of a gc handle, `None` if it was collected
+    fn get_gc_handle_target(&self, handle: usize) -> Result<Option<UnityObject>, RuntimeError>;
+
+    /// frees a gc handle, after which it may not be used anymore
+    fn free_gc_handle(&self, handle: usize) -> Result<(), RuntimeError>;
 }
 
+static RUNTIME: OnceLock<Box<dyn Runtime>> = OnceLock::new();
 
 /// looks up the runtime
-pub fn get_runtime() -> Result<Box<dyn Runtime>, RuntimeError> {
+///
+/// the lookup only happens once, every call after that returns the same runtime
+pub fn get_runtime() -> Result<&'static dyn Runtime, RuntimeError> {
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime.as_ref());
+    }
+
+    let runtime = load_runtime()?;
+    Ok(RUNTIME.get_or_init(|| runtime).as_ref())
+}
+
+fn load_runtime() -> Result<Box<dyn Runtime>, RuntimeError> {
     let exe_path = std::env::current_exe()?;
     if !is_unity(&exe_path)? {
         return Err(RuntimeError::NotUnity);
@@ -82,7 +400,7 @@ pub fn get_runtime() -> Result<Box<dyn Runtime>, RuntimeError> {
 
     let base_path = exe_path
         .parent()
-        .ok_or(RuntimeError::BasePathNotFound)?
+        .ok_or(PathError::BasePathNotFound)?
         .to_path_buf();
     let data_path =
         utils::path::get_data_path(&exe_path)?;
@@ -98,16 +416,16 @@ pub fn get_runtime() -> Result<Box<dyn Runtime>, RuntimeError> {
     }
 }
 
-fn is_unity(file_path: &PathBuf) -> Result<bool, RuntimeError> {
+fn is_unity(file_path: &Path) -> Result<bool, RuntimeError> {
     let file_name = file_path
         .file_stem()
-        .ok_or(RuntimeError::BasePathNotFound)?
+        .ok_or(PathError::BasePathNotFound)?
         .to_str()
-        .ok_or(RuntimeError::BasePathNotFound)?;
+        .ok_or(PathError::BasePathNotFound)?;
 
     let base_folder = file_path
         .parent()
-        .ok_or(RuntimeError::BasePathNotFound)?;
+        .ok_or(PathError::BasePathNotFound)?;
 
     let data_path = base_folder.join(format!("{}_Data", file_name));
 
@@ -0,0 +1,21 @@
+use std::ffi::{c_char, CStr};
+
+use crate::runtime::RuntimeError;
+
+/// copies a nul terminated string handed out by the runtime into a rust String
+///
+/// # Arguments
+///
+/// * `ptr` - the string returned by the runtime
+/// * `function` - the name of the function that returned it, used for the error
+///
+/// # Errors
+///
+/// * `RuntimeError::ReturnedNull` - if `ptr` is null
+pub(crate) fn c_str_to_string(ptr: *const c_char, function: &'static str) -> Result<String, RuntimeError> {
+    if ptr.is_null() {
+        return Err(RuntimeError::ReturnedNull(function));
+    }
+
+    Ok(unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
+}
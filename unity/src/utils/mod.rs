@@ -1 +1,2 @@
+pub mod ffi;
 pub mod path;
\ No newline at end of file
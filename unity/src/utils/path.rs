@@ -1,13 +1,20 @@
-use std::{path::PathBuf, error};
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
+/// failures while locating the game and its runtime on disk
 #[derive(Debug, Error)]
 pub enum PathError {
-   #[error("Failed to find Mono")]
+    #[error("Failed to find Mono")]
     MonoNotFound,
     #[error("Failed to find DataPath")]
     DataPathNotFound,
+    #[error("Failed to find Base Path")]
+    BasePathNotFound,
+    #[error("Failed to find GameAssembly")]
+    GameAssemblyNotFound,
+    #[error("Invalid file name: {0}")]
+    InvalidFileName(PathBuf),
 }
 
 /// joins a path with a file name, and appends the platform specific extension.
@@ -22,19 +29,20 @@ pub enum PathError {
 /// ```
 /// use std::path::PathBuf;
 /// 
-/// use crate::utils::path::join_path;
+/// use unity_rs::join_dll_path;
 /// 
-/// let path = PathBuf::from("C:\\Users\\User\\Desktop");
+/// let path = PathBuf::from("/home/user/Desktop");
 /// let file_name = "test";
 /// 
-/// let joined_path = join_dll_path(&path, file_name);
+/// let joined_path = join_dll_path!(&path, file_name);
 /// 
 /// #[cfg(target_os = "windows")]
-/// assert_eq!(joined_path, PathBuf::from("C:\\Users\\User\\Desktop\\test.dll"));
+/// assert_eq!(joined_path, path.join("test.dll"));
 /// #[cfg(target_os = "linux")]
-/// assert_eq!(joined_path, PathBuf::from("C:\\Users\\User\\Desktop\\test.so"));
+/// assert_eq!(joined_path, path.join("test.so"));
 /// #[cfg(target_os = "macos")]
-/// assert_eq!(joined_path, PathBuf::from("C:\\Users\\User\\Desktop\\test.dylib"));
+/// assert_eq!(joined_path, path.join("test.dylib"));
+/// ```
 #[macro_export]
 macro_rules! join_dll_path {
     //take two arguments, a PathBuf and a file name
@@ -46,10 +54,10 @@ macro_rules! join_dll_path {
     };
 }
 
-pub fn find_mono(game_base_path: &PathBuf, game_data_path: &PathBuf) -> Result<PathBuf, Box<dyn error::Error>> {
-    let folder_names = vec!["MonoBleedingEdge", "Mono", "MonoBleedingEdge.x64", "MonoBleedingEdge.x86"];
+pub fn find_mono(game_base_path: &Path, game_data_path: &Path) -> Result<PathBuf, PathError> {
+    let folder_names = ["MonoBleedingEdge", "Mono", "MonoBleedingEdge.x64", "MonoBleedingEdge.x86"];
     // workaround for weird path behavior, extension will be replaced with platform specific extension.
-    let lib_names = vec!["libmono.so", "mono.dll", "mono-2.0-bdwgc.dll", "mono-2.0-sgen.dll", "mono-2.0-boehm.dll", "libmonobdwgc-2.0.so"];
+    let lib_names = ["libmono.so", "mono.dll", "mono-2.0-bdwgc.dll", "mono-2.0-sgen.dll", "mono-2.0-boehm.dll", "libmonobdwgc-2.0.so"];
 
     for folder_name in folder_names.iter() {
         for lib_name in lib_names.iter() {
@@ -80,22 +88,22 @@ pub fn find_mono(game_base_path: &PathBuf, game_data_path: &PathBuf) -> Result<P
         }
     }
 
-    Err(Box::new(PathError::MonoNotFound))
+    Err(PathError::MonoNotFound)
 }
 
-pub fn get_data_path(file_path: &PathBuf) -> Result<PathBuf, Box<dyn error::Error>> {
+pub fn get_data_path(file_path: &Path) -> Result<PathBuf, PathError> {
     let file_name = file_path.file_stem()
-        .ok_or_else(|| PathError::DataPathNotFound)?
+        .ok_or(PathError::DataPathNotFound)?
         .to_str()
-        .ok_or_else(|| PathError::DataPathNotFound)?;
+        .ok_or(PathError::DataPathNotFound)?;
 
     let base_folder = file_path.parent()
-        .ok_or_else(|| PathError::DataPathNotFound)?;
+        .ok_or(PathError::DataPathNotFound)?;
 
     let data_path = base_folder.join(format!("{}_Data", file_name));
 
     match data_path.exists() {
         true => Ok(data_path),
-        false => Err(Box::new(PathError::DataPathNotFound)),
+        false => Err(PathError::DataPathNotFound),
     }
 }
\ No newline at end of file